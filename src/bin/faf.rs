@@ -0,0 +1,317 @@
+//! `faf` command-line tool
+//!
+//! Exit codes: 0 success, 1 check failed (invalid file, no file found,
+//! `fmt --check` would reformat), 2 usage, I/O or parse error (including
+//! files `fmt` cannot lay out).
+//!
+//! Commands without a path use the file named by `FAF_PATH`, if set, or
+//! the nearest FAF file above the current directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use faf_rust_sdk::{
+    compile_fafb, compress, compress_to_budget, diff, export, export_to, find_faf_file_detailed,
+    find_faf_file_with, format, parse, stringify, validate, write_atomic, CompressionLevel,
+    DiscoveryOptions, ExportTarget, FafFile, FormatOptions, HeuristicTokenizer, ScoringModel,
+    FAF_PATH_ENV,
+};
+
+#[derive(Parser)]
+#[command(name = "faf", version, about = "Work with project.faf files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Validate a FAF file
+    Validate {
+        /// FAF file (default: nearest project.faf)
+        path: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Show the completeness score and the slots to fill next
+    Score { path: Option<PathBuf> },
+    /// Print a compressed copy for a token-limited context window
+    Compress {
+        path: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = Level::Standard, conflicts_with = "budget")]
+        level: Level,
+        /// Fit within this many tokens instead of a fixed level
+        #[arg(long)]
+        budget: Option<usize>,
+    },
+    /// Print the path of the nearest FAF file (or `FAF_PATH`)
+    Find {
+        /// Directory to search upward from (default: current directory)
+        dir: Option<PathBuf>,
+    },
+    /// Rewrite a FAF file in canonical layout
+    Fmt {
+        path: Option<PathBuf>,
+        /// Exit 1 instead of writing if the file would change
+        #[arg(long)]
+        check: bool,
+    },
+    /// Show field-level changes between two FAF files
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+    },
+    /// Compile to the .fafb binary format
+    Compile {
+        path: Option<PathBuf>,
+        /// Output file (default: next to the source with a .fafb extension)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Write agent instruction files (CLAUDE.md, AGENTS.md, ...)
+    Export {
+        path: Option<PathBuf>,
+        /// claude-md, agents-md, cursorrules or copilot
+        #[arg(long = "to", required = true)]
+        targets: Vec<String>,
+        /// Print to stdout instead of updating the files
+        #[arg(long)]
+        stdout: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DiffFormat {
+    Text,
+    Summary,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Level {
+    Minimal,
+    Standard,
+    Full,
+}
+
+/// Success, or a failed check (exit 1)
+type Outcome = Result<bool, String>;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let outcome = match cli.command {
+        Command::Validate { path, format } => run_validate(path, format),
+        Command::Score { path } => run_score(path),
+        Command::Compress {
+            path,
+            level,
+            budget,
+        } => run_compress(path, level, budget),
+        Command::Find { dir } => run_find(dir),
+        Command::Fmt { path, check } => run_fmt(path, check),
+        Command::Diff { old, new, format } => run_diff(&old, &new, format),
+        Command::Compile { path, output } => run_compile(path, output),
+        Command::Export {
+            path,
+            targets,
+            stdout,
+        } => run_export(path, &targets, stdout),
+    };
+    match outcome {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(message) => {
+            eprintln!("faf: {}", message);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Discovery for commands without a path: `FAF_PATH` wins if set
+fn discovery() -> DiscoveryOptions {
+    DiscoveryOptions::new().env_override(FAF_PATH_ENV)
+}
+
+/// The given path, or the nearest FAF file above the current directory
+fn locate(path: Option<PathBuf>) -> Result<PathBuf, String> {
+    path.or_else(|| find_faf_file_with::<PathBuf>(None, &discovery()).map(|found| found.path))
+        .ok_or_else(|| "no project.faf found".to_string())
+}
+
+fn load(path: &Path) -> Result<(FafFile, String), String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut faf = parse(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    faf.path = Some(path.to_string_lossy().to_string());
+    Ok((faf, content))
+}
+
+fn run_validate(path: Option<PathBuf>, format: OutputFormat) -> Outcome {
+    let path = locate(path)?;
+    let (faf, _) = load(&path)?;
+    let result = validate(&faf);
+    match format {
+        OutputFormat::Text => {
+            for d in &result.diagnostics {
+                println!("{}: {} {}: {}", d.severity, d.id, d.name, d.message);
+                if let Some(fix) = &d.fix {
+                    println!("  fix: {}", fix);
+                }
+            }
+            let status = if result.valid { "valid" } else { "invalid" };
+            println!("{}: {} ({}%)", path.display(), status, result.score);
+        }
+        OutputFormat::Json => {
+            let diagnostics: Vec<_> = result
+                .diagnostics
+                .iter()
+                .map(|d| {
+                    serde_json::json!({
+                        "id": d.id,
+                        "name": d.name,
+                        "severity": d.severity.to_string(),
+                        "message": d.message,
+                        "fix": d.fix,
+                    })
+                })
+                .collect();
+            let report = serde_json::json!({
+                "path": path,
+                "valid": result.valid,
+                "score": result.score,
+                "diagnostics": diagnostics,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?
+            );
+        }
+    }
+    Ok(result.valid)
+}
+
+fn run_score(path: Option<PathBuf>) -> Outcome {
+    let (faf, _) = load(&locate(path)?)?;
+    let breakdown = ScoringModel::default().score(&faf.data);
+    let filled = breakdown.slots.iter().filter(|s| s.is_complete()).count();
+    println!(
+        "{}%  ({}/{} slots)",
+        breakdown.score,
+        filled,
+        breakdown.slots.len()
+    );
+    for slot in breakdown.next_to_fill().iter().take(5) {
+        println!("  fill next: {}", slot.path);
+    }
+    Ok(true)
+}
+
+fn run_compress(path: Option<PathBuf>, level: Level, budget: Option<usize>) -> Outcome {
+    let (faf, _) = load(&locate(path)?)?;
+    let data = match budget {
+        Some(budget) => compress_to_budget(&faf, budget, &HeuristicTokenizer::default()).data,
+        None => {
+            let level = match level {
+                Level::Minimal => CompressionLevel::Minimal,
+                Level::Standard => CompressionLevel::Standard,
+                Level::Full => CompressionLevel::Full,
+            };
+            compress(&faf, level)
+        }
+    };
+    let out = stringify(&FafFile::new(data)).map_err(|e| e.to_string())?;
+    print!("{}", out);
+    Ok(true)
+}
+
+fn run_find(dir: Option<PathBuf>) -> Outcome {
+    let report = find_faf_file_detailed(dir, &discovery());
+    for path in &report.shadowed {
+        eprintln!("faf: warning: {} is shadowed", path.display());
+    }
+    for entry in &report.unreadable {
+        eprintln!(
+            "faf: warning: cannot read {}: {}",
+            entry.path.display(),
+            entry.kind
+        );
+    }
+    match report.found {
+        Some(found) => {
+            println!("{}", found.path.display());
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+fn run_fmt(path: Option<PathBuf>, check: bool) -> Outcome {
+    let path = locate(path)?;
+    let content = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let formatted = format(&content, &FormatOptions::default())
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    if formatted == content {
+        return Ok(true);
+    }
+    if check {
+        println!("{}: would reformat", path.display());
+        return Ok(false);
+    }
+    write_atomic(&path, formatted.as_bytes()).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(true)
+}
+
+fn run_diff(old: &Path, new: &Path, format: DiffFormat) -> Outcome {
+    let (old, _) = load(old)?;
+    let (new, _) = load(new)?;
+    let changes = diff(&old.data, &new.data);
+    match format {
+        DiffFormat::Text => print!("{}", changes),
+        DiffFormat::Summary => println!("{}", changes.summary()),
+        DiffFormat::Json => println!("{}", changes.to_json().map_err(|e| e.to_string())?),
+    }
+    Ok(true)
+}
+
+fn run_compile(path: Option<PathBuf>, output: Option<PathBuf>) -> Outcome {
+    let path = locate(path)?;
+    let (faf, content) = load(&path)?;
+    let bytes = compile_fafb(&faf, &content).map_err(|e| e.to_string())?;
+    let output = output.unwrap_or_else(|| path.with_extension("fafb"));
+    write_atomic(&output, &bytes).map_err(|e| format!("{}: {}", output.display(), e))?;
+    println!("{} ({} bytes)", output.display(), bytes.len());
+    Ok(true)
+}
+
+fn run_export(path: Option<PathBuf>, targets: &[String], stdout: bool) -> Outcome {
+    let targets = targets
+        .iter()
+        .map(|name| {
+            ExportTarget::from_name(name).ok_or_else(|| format!("unknown export target: {}", name))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let path = locate(path)?;
+    let (faf, _) = load(&path)?;
+    let root = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    for target in targets {
+        if stdout {
+            print!("{}", export(&faf, target));
+        } else {
+            let written = export_to(&faf, target, root).map_err(|e| e.to_string())?;
+            println!("{}", written.display());
+        }
+    }
+    Ok(true)
+}
@@ -0,0 +1,544 @@
+//! Cargo manifest detection - enrich FAF data from Cargo.toml
+
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::types::{FafData, InstantContext, Stack};
+
+/// Cargo detection errors
+#[derive(Error, Debug)]
+pub enum CargoError {
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("Invalid Cargo.toml: {0}")]
+    TomlError(#[from] toml::de::Error),
+
+    #[error("Invalid workspace member pattern: {0}")]
+    PatternError(#[from] glob::PatternError),
+
+    #[error("Cargo.toml has neither [package] nor [workspace]")]
+    NotACrate,
+}
+
+/// A single crate - the root package or a workspace member
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoCrate {
+    /// Crate name from `[package]`
+    pub name: String,
+    /// Directory relative to the detection root (empty for the root package)
+    pub path: String,
+    /// Entry points that exist on disk (`src/lib.rs`, `src/main.rs`), relative to the root
+    pub key_files: Vec<String>,
+}
+
+/// Facts detected from a Cargo.toml (single crate or workspace)
+#[derive(Debug, Clone, Default)]
+pub struct CargoProject {
+    /// Root package name (None for a virtual workspace)
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub license: Option<String>,
+    pub edition: Option<String>,
+    /// Union of keywords across all crates
+    pub keywords: Vec<String>,
+    /// Union of categories across all crates
+    pub categories: Vec<String>,
+    /// Root package followed by workspace members
+    pub crates: Vec<CargoCrate>,
+    /// True if the manifest has a `[workspace]` table
+    pub is_workspace: bool,
+    /// True if any crate has a `benches/` directory
+    pub has_benches: bool,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Option<Package>,
+    workspace: Option<Workspace>,
+    lib: Option<Target>,
+}
+
+#[derive(Deserialize)]
+struct Workspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    package: Option<WorkspacePackage>,
+}
+
+#[derive(Deserialize, Default)]
+struct WorkspacePackage {
+    version: Option<String>,
+    license: Option<String>,
+    description: Option<String>,
+    edition: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    categories: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    name: String,
+    version: Option<Inheritable<String>>,
+    license: Option<Inheritable<String>>,
+    description: Option<Inheritable<String>>,
+    edition: Option<Inheritable<String>>,
+    keywords: Option<Inheritable<Vec<String>>>,
+    categories: Option<Inheritable<Vec<String>>>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    path: Option<String>,
+}
+
+/// A package field that may be `field.workspace = true`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Inheritable<T> {
+    Value(T),
+    #[allow(dead_code)]
+    Workspace {
+        workspace: bool,
+    },
+}
+
+impl<T: Clone> Inheritable<T> {
+    fn resolve(field: &Option<Self>, inherited: Option<&T>) -> Option<T> {
+        match field {
+            Some(Inheritable::Value(v)) => Some(v.clone()),
+            Some(Inheritable::Workspace { .. }) => inherited.cloned(),
+            None => None,
+        }
+    }
+}
+
+/// Detect Rust project facts from the Cargo.toml in `root`
+///
+/// Handles single crates, virtual workspaces and workspaces with a root
+/// package. Workspace members may use glob patterns (`crates/*`).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::detect_cargo;
+///
+/// let cargo = detect_cargo(".").unwrap();
+/// println!("Crates: {}", cargo.crates.len());
+/// ```
+pub fn detect_cargo<P: AsRef<Path>>(root: P) -> Result<CargoProject, CargoError> {
+    let root = root.as_ref();
+    let manifest = read_manifest(&root.join("Cargo.toml"))?;
+
+    if manifest.package.is_none() && manifest.workspace.is_none() {
+        return Err(CargoError::NotACrate);
+    }
+
+    let ws_package = manifest
+        .workspace
+        .as_ref()
+        .and_then(|ws| ws.package.as_ref());
+    let empty = WorkspacePackage::default();
+    let ws = ws_package.unwrap_or(&empty);
+
+    let mut project = CargoProject {
+        is_workspace: manifest.workspace.is_some(),
+        version: ws.version.clone(),
+        license: ws.license.clone(),
+        description: ws.description.clone(),
+        edition: ws.edition.clone(),
+        ..Default::default()
+    };
+    push_unique(&mut project.keywords, &ws.keywords);
+    push_unique(&mut project.categories, &ws.categories);
+
+    if let Some(package) = &manifest.package {
+        project.name = Some(package.name.clone());
+        let resolve = |field: &Option<Inheritable<String>>, inherited: &Option<String>| {
+            Inheritable::resolve(field, inherited.as_ref())
+        };
+        project.version = resolve(&package.version, &ws.version).or(project.version);
+        project.license = resolve(&package.license, &ws.license).or(project.license);
+        project.description =
+            resolve(&package.description, &ws.description).or(project.description);
+        project.edition = resolve(&package.edition, &ws.edition).or(project.edition);
+        add_crate(&mut project, root, "", package, manifest.lib.as_ref(), ws);
+    }
+
+    if let Some(workspace) = &manifest.workspace {
+        for dir in member_dirs(root, workspace)? {
+            // `members = ["."]`: the root package was added above
+            if manifest.package.is_some() && same_dir(&dir, root) {
+                continue;
+            }
+            let rel = relative_path(root, &dir);
+            let member = read_manifest(&dir.join("Cargo.toml"))?;
+            if let Some(package) = &member.package {
+                add_crate(&mut project, root, &rel, package, member.lib.as_ref(), ws);
+            }
+        }
+    }
+
+    Ok(project)
+}
+
+impl CargoProject {
+    /// Merge detected facts into existing FAF data
+    ///
+    /// Only fills gaps: scalar fields a human has already set are left
+    /// alone, existing commands keep their values, and `key_files`/`tags`
+    /// only gain entries that are not already listed.
+    pub fn merge_into(&self, data: &mut FafData) {
+        let project = &mut data.project;
+        if project.name.is_empty() {
+            if let Some(name) = &self.name {
+                project.name = name.clone();
+            }
+        }
+        fill(&mut project.goal, &self.description);
+        fill(&mut project.main_language, &Some("Rust".to_string()));
+        fill(&mut project.version, &self.version);
+        fill(&mut project.license, &self.license);
+
+        let stack = data.stack.get_or_insert_with(Stack::default);
+        fill(&mut stack.build_tool, &Some("Cargo".to_string()));
+
+        let ic = data
+            .instant_context
+            .get_or_insert_with(InstantContext::default);
+        let key_files: Vec<String> = self
+            .crates
+            .iter()
+            .flat_map(|c| c.key_files.iter().cloned())
+            .collect();
+        push_unique(&mut ic.key_files, &key_files);
+        for (name, command) in self.commands() {
+            ic.commands.entry(name.to_string()).or_insert(command);
+        }
+
+        push_unique(&mut data.tags, &self.keywords);
+        push_unique(&mut data.tags, &self.categories);
+    }
+
+    /// Cargo commands for this project (`bench` only when benches exist)
+    pub fn commands(&self) -> Vec<(&'static str, String)> {
+        let scope = if self.is_workspace {
+            " --workspace"
+        } else {
+            ""
+        };
+        let mut commands = vec![
+            ("build", format!("cargo build{}", scope)),
+            ("test", format!("cargo test{}", scope)),
+            (
+                "clippy",
+                format!("cargo clippy{} --all-targets -- -D warnings", scope),
+            ),
+        ];
+        if self.has_benches {
+            commands.push(("bench", format!("cargo bench{}", scope)));
+        }
+        commands
+    }
+}
+
+/// Detect Cargo.toml in `root` and merge it into `data` in one call
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{enrich_from_cargo, parse_file};
+///
+/// let mut faf = parse_file("project.faf").unwrap();
+/// enrich_from_cargo(&mut faf.data, ".").unwrap();
+/// ```
+pub fn enrich_from_cargo<P: AsRef<Path>>(data: &mut FafData, root: P) -> Result<(), CargoError> {
+    detect_cargo(root)?.merge_into(data);
+    Ok(())
+}
+
+fn read_manifest(path: &Path) -> Result<Manifest, CargoError> {
+    let content = fs::read_to_string(path)?;
+    Ok(toml::from_str(&content)?)
+}
+
+fn member_dirs(root: &Path, workspace: &Workspace) -> Result<Vec<PathBuf>, CargoError> {
+    let excluded: Vec<PathBuf> = workspace.exclude.iter().map(|e| root.join(e)).collect();
+    let mut dirs = Vec::new();
+
+    for pattern in &workspace.members {
+        // The root is a literal path, only the member pattern is a glob
+        let full = Path::new(&glob::Pattern::escape(&root.to_string_lossy())).join(pattern);
+        for dir in glob::glob(&full.to_string_lossy())?.flatten() {
+            if dir.join("Cargo.toml").is_file() && !excluded.contains(&dir) && !dirs.contains(&dir)
+            {
+                dirs.push(dir);
+            }
+        }
+    }
+
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// `root/.` and `root` are the same directory
+fn same_dir(a: &Path, b: &Path) -> bool {
+    a.components().eq(b.components())
+        || matches!((fs::canonicalize(a), fs::canonicalize(b)), (Ok(a), Ok(b)) if a == b)
+}
+
+fn add_crate(
+    project: &mut CargoProject,
+    root: &Path,
+    rel: &str,
+    package: &Package,
+    lib: Option<&Target>,
+    ws: &WorkspacePackage,
+) {
+    let dir = root.join(rel);
+    let join = |file: &str| {
+        if rel.is_empty() {
+            file.to_string()
+        } else {
+            format!("{}/{}", rel, file)
+        }
+    };
+
+    let lib_path = lib
+        .and_then(|t| t.path.clone())
+        .unwrap_or_else(|| "src/lib.rs".to_string());
+    let key_files = [lib_path.as_str(), "src/main.rs"]
+        .iter()
+        .filter(|file| dir.join(file).is_file())
+        .map(|file| join(file))
+        .collect();
+
+    if let Some(keywords) = Inheritable::resolve(&package.keywords, Some(&ws.keywords)) {
+        push_unique(&mut project.keywords, &keywords);
+    }
+    if let Some(categories) = Inheritable::resolve(&package.categories, Some(&ws.categories)) {
+        push_unique(&mut project.categories, &categories);
+    }
+    if dir.join("benches").is_dir() {
+        project.has_benches = true;
+    }
+
+    project.crates.push(CargoCrate {
+        name: package.name.clone(),
+        path: rel.to_string(),
+        key_files,
+    });
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn fill(slot: &mut Option<String>, value: &Option<String>) {
+    if slot.is_none() {
+        slot.clone_from(value);
+    }
+}
+
+fn push_unique(list: &mut Vec<String>, items: &[String]) {
+    for item in items {
+        if !list.contains(item) {
+            list.push(item.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, rel: &str, content: &str) {
+        let path = dir.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_detect_single_crate() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "Cargo.toml",
+            r#"
+[package]
+name = "demo"
+version = "0.3.0"
+license = "MIT"
+edition = "2021"
+keywords = ["cli", "yaml"]
+categories = ["parsing"]
+"#,
+        );
+        write(dir.path(), "src/lib.rs", "");
+        write(dir.path(), "benches/speed.rs", "");
+
+        let cargo = detect_cargo(dir.path()).unwrap();
+        assert_eq!(cargo.name.as_deref(), Some("demo"));
+        assert_eq!(cargo.version.as_deref(), Some("0.3.0"));
+        assert!(!cargo.is_workspace);
+        assert!(cargo.has_benches);
+        assert_eq!(cargo.crates[0].key_files, vec!["src/lib.rs"]);
+        assert!(cargo.commands().iter().any(|(name, _)| *name == "bench"));
+    }
+
+    #[test]
+    fn test_detect_workspace() {
+        let dir = TempDir::new().unwrap();
+        // Glob metacharacters in the root must not break member matching
+        let root = dir.path().join("work [x]");
+        write(
+            &root,
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["crates/*"]
+exclude = ["crates/skip"]
+
+[workspace.package]
+version = "2.0.0"
+license = "Apache-2.0"
+"#,
+        );
+        write(
+            &root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"core\"\nversion.workspace = true\nkeywords = [\"core\"]\n",
+        );
+        write(&root, "crates/core/src/lib.rs", "");
+        write(
+            &root,
+            "crates/cli/Cargo.toml",
+            "[package]\nname = \"cli\"\n",
+        );
+        write(&root, "crates/cli/src/main.rs", "");
+        write(
+            &root,
+            "crates/skip/Cargo.toml",
+            "[package]\nname = \"skip\"\n",
+        );
+
+        let cargo = detect_cargo(&root).unwrap();
+        assert!(cargo.is_workspace);
+        assert!(cargo.name.is_none());
+        assert_eq!(cargo.version.as_deref(), Some("2.0.0"));
+        let names: Vec<_> = cargo.crates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["cli", "core"]);
+        assert_eq!(cargo.crates[0].key_files, vec!["crates/cli/src/main.rs"]);
+        assert_eq!(cargo.crates[1].key_files, vec!["crates/core/src/lib.rs"]);
+        assert_eq!(cargo.keywords, vec!["core"]);
+        assert!(cargo
+            .commands()
+            .iter()
+            .all(|(_, cmd)| cmd.contains("--workspace")));
+    }
+
+    #[test]
+    fn test_detect_root_package_as_member() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\".\", \"crates/*\"]\n",
+        );
+        write(root, "src/main.rs", "");
+        write(root, "crates/util/Cargo.toml", "[package]\nname = \"util\"\n");
+        write(root, "crates/util/src/lib.rs", "");
+
+        let cargo = detect_cargo(root).unwrap();
+        let names: Vec<_> = cargo.crates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "util"]);
+
+        // A glob reaching back to the root is the same package
+        write(
+            root,
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\"crates/..\", \"crates/*\"]\n",
+        );
+        let cargo = detect_cargo(root).unwrap();
+        let names: Vec<_> = cargo.crates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "util"]);
+    }
+
+    #[test]
+    fn test_merge_keeps_human_fields() {
+        let mut faf = parse(
+            r#"
+faf_version: 2.5.0
+project:
+  name: hand-written
+  version: 9.9.9
+instant_context:
+  key_files:
+    - README.md
+  commands:
+    test: cargo nextest run
+tags:
+  - yaml
+"#,
+        )
+        .unwrap();
+
+        let cargo = CargoProject {
+            name: Some("demo".to_string()),
+            version: Some("0.3.0".to_string()),
+            license: Some("MIT".to_string()),
+            keywords: vec!["cli".to_string(), "yaml".to_string()],
+            crates: vec![CargoCrate {
+                name: "demo".to_string(),
+                path: String::new(),
+                key_files: vec!["src/lib.rs".to_string()],
+            }],
+            ..Default::default()
+        };
+        cargo.merge_into(&mut faf.data);
+
+        assert_eq!(faf.project_name(), "hand-written");
+        assert_eq!(faf.data.project.version.as_deref(), Some("9.9.9"));
+        assert_eq!(faf.data.project.license.as_deref(), Some("MIT"));
+        assert_eq!(faf.data.stack.unwrap().build_tool.as_deref(), Some("Cargo"));
+        let ic = faf.data.instant_context.unwrap();
+        assert_eq!(ic.key_files, vec!["README.md", "src/lib.rs"]);
+        assert_eq!(ic.commands["test"], "cargo nextest run");
+        assert_eq!(ic.commands["build"], "cargo build");
+        assert_eq!(faf.data.tags, vec!["yaml", "cli"]);
+    }
+
+    #[test]
+    fn test_detect_own_manifest() {
+        let cargo = detect_cargo(env!("CARGO_MANIFEST_DIR")).unwrap();
+        assert_eq!(cargo.name.as_deref(), Some("faf-rust-sdk"));
+        assert_eq!(cargo.license.as_deref(), Some("MIT"));
+        assert!(cargo.keywords.contains(&"faf".to_string()));
+        assert_eq!(cargo.crates[0].key_files, vec!["src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_not_a_crate() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "Cargo.toml", "[dependencies]\n");
+        assert!(matches!(
+            detect_cargo(dir.path()),
+            Err(CargoError::NotACrate)
+        ));
+    }
+}
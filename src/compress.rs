@@ -1,7 +1,63 @@
 //! FAF compression for token optimization
 
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
 use crate::types::*;
-use crate::parser::FafFile;
+use crate::parser::{FafError, FafFile};
+use crate::tokens::Tokenizer;
+
+/// Fields every compressed file keeps
+const REQUIRED: &[&str] = &["faf_version", "project.name"];
+
+/// Field priority for budgeted compression, most important first
+///
+/// `faf_version` and `project.name` are always kept. Lists and maps are
+/// added entry by entry.
+const BUDGET_PRIORITY: &[&str] = &[
+    "project.goal",
+    "instant_context.what_building",
+    "instant_context.tech_stack",
+    "project.main_language",
+    "instant_context.key_files",
+    "instant_context.commands",
+    "stack.backend",
+    "stack.frontend",
+    "stack.database",
+    "stack.build_tool",
+    "stack.testing",
+    "stack.infrastructure",
+    "stack.cicd",
+    "ai_score",
+    "human_context.what",
+    "human_context.why",
+    "human_context.who",
+    "human_context.how",
+    "human_context.where",
+    "human_context.when",
+    "instant_context.deployment",
+    "project.approach",
+    "project.version",
+    "project.license",
+    "preferences.quality_bar",
+    "preferences.testing",
+    "preferences.code_style",
+    "preferences.documentation",
+    "state.phase",
+    "state.focus",
+    "state.version",
+    "state.milestones",
+    "ai_tldr",
+    "context_quality.slots_filled",
+    "context_quality.confidence",
+    "context_quality.handoff_ready",
+    "context_quality.missing_context",
+    "tags",
+    "ai_confidence",
+];
 
 /// Compression levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,6 +70,168 @@ pub enum CompressionLevel {
     Full = 3,
 }
 
+/// Field selection for custom compression ([`compress_with_profile`])
+///
+/// Paths are dotted YAML names (`instant_context.commands.test`,
+/// `human_context.why`). A path selects the field and everything below it.
+/// `include` (empty keeps everything) is applied first, then `exclude`,
+/// then `caps` limit list and map entries. `faf_version` and
+/// `project.name` are always kept.
+///
+/// ```yaml
+/// include:
+///   - project
+///   - instant_context
+///   - preferences
+/// exclude:
+///   - instant_context.deployment
+/// caps:
+///   instant_context.key_files: 10
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressionProfile {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub caps: BTreeMap<String, usize>,
+}
+
+impl CompressionProfile {
+    /// Empty profile (keeps everything)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a profile from YAML
+    pub fn from_yaml(content: &str) -> Result<Self, FafError> {
+        Ok(serde_yaml::from_str(content)?)
+    }
+
+    /// Load a profile from a YAML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, FafError> {
+        Self::from_yaml(&fs::read_to_string(path)?)
+    }
+
+    /// Keep `path` (and everything below it)
+    pub fn include(mut self, path: impl Into<String>) -> Self {
+        self.include.push(path.into());
+        self
+    }
+
+    /// Drop `path` (and everything below it)
+    pub fn exclude(mut self, path: impl Into<String>) -> Self {
+        self.exclude.push(path.into());
+        self
+    }
+
+    /// Keep at most `max` entries of the list or map at `path`
+    pub fn cap(mut self, path: impl Into<String>, max: usize) -> Self {
+        self.caps.insert(path.into(), max);
+        self
+    }
+
+    /// Apply the profile to FAF data
+    pub fn apply(&self, data: &FafData) -> FafData {
+        let source = serde_yaml::to_value(data).unwrap_or(Value::Null);
+        let filtered = self.filter(&source, "", Scope::Partial);
+        serde_yaml::from_value(filtered).unwrap_or_else(|_| required_only(data))
+    }
+
+    fn filter(&self, value: &Value, prefix: &str, scope: Scope) -> Value {
+        let cap = self.caps.get(prefix).copied().unwrap_or(usize::MAX);
+
+        match value {
+            Value::Mapping(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                if self.caps.contains_key(prefix) {
+                    entries.sort_by_key(|(k, _)| k.as_str().map(str::to_string));
+                }
+                let mut out = Mapping::new();
+                for (key, child) in entries {
+                    if out.len() >= cap {
+                        break;
+                    }
+                    let path = join(prefix, key.as_str().unwrap_or_default());
+                    let child_scope = if REQUIRED.contains(&path.as_str()) {
+                        Scope::All
+                    } else if scope == Scope::RequiredOnly || covers(&self.exclude, &path) {
+                        Scope::RequiredOnly
+                    } else if scope == Scope::All
+                        || self.include.is_empty()
+                        || covers(&self.include, &path)
+                    {
+                        Scope::All
+                    } else {
+                        Scope::Partial
+                    };
+
+                    let below = |paths: &[&str]| {
+                        paths.iter().any(|p| p.starts_with(&format!("{}.", path)))
+                    };
+                    let include: Vec<&str> = self.include.iter().map(String::as_str).collect();
+                    let reachable = match child_scope {
+                        Scope::All => true,
+                        Scope::Partial => below(&include) || below(REQUIRED),
+                        Scope::RequiredOnly => below(REQUIRED),
+                    };
+                    if !reachable {
+                        continue;
+                    }
+
+                    let filtered = self.filter(child, &path, child_scope);
+                    let empty = matches!(&filtered, Value::Mapping(m) if m.is_empty());
+                    if child_scope != Scope::All && empty {
+                        continue;
+                    }
+                    out.insert(key.clone(), filtered);
+                }
+                Value::Mapping(out)
+            }
+            Value::Sequence(items) => Value::Sequence(items.iter().take(cap).cloned().collect()),
+            other => other.clone(),
+        }
+    }
+}
+
+/// How much of a subtree a profile keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    /// Everything not excluded
+    All,
+    /// Only included paths below this point
+    Partial,
+    /// Only required fields below this point
+    RequiredOnly,
+}
+
+/// True if `path` is one of `paths` or below one of them
+fn covers(paths: &[String], path: &str) -> bool {
+    paths
+        .iter()
+        .any(|p| p == path || path.starts_with(&format!("{}.", p)))
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn required_only(data: &FafData) -> FafData {
+    FafData {
+        faf_version: data.faf_version.clone(),
+        project: Project {
+            name: data.project.name.clone(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
 /// Compress FAF to specified level
 ///
 /// # Example
@@ -49,6 +267,23 @@ pub fn compress(faf: &FafFile, level: CompressionLevel) -> FafData {
     }
 }
 
+/// Compress FAF to the fields a profile selects
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{compress_with_profile, parse, CompressionProfile};
+///
+/// let faf = parse("faf_version: 2.5.0\nproject:\n  name: test\n  goal: Testing\ntags: [a, b, c]").unwrap();
+/// let profile = CompressionProfile::new().exclude("project.goal").cap("tags", 2);
+/// let compressed = compress_with_profile(&faf, &profile);
+/// assert!(compressed.project.goal.is_none());
+/// assert_eq!(compressed.tags, vec!["a", "b"]);
+/// ```
+pub fn compress_with_profile(faf: &FafFile, profile: &CompressionProfile) -> FafData {
+    profile.apply(&faf.data)
+}
+
 fn compress_minimal(faf: &FafFile) -> FafData {
     FafData {
         faf_version: faf.data.faf_version.clone(),
@@ -103,6 +338,9 @@ fn compress_standard(faf: &FafFile) -> FafData {
 }
 
 /// Get estimated token count for compression level
+///
+/// These are fixed preset figures. Use `estimate_tokens_for` on the
+/// compressed data for a count based on its actual content.
 pub fn estimate_tokens(level: CompressionLevel) -> usize {
     match level {
         CompressionLevel::Minimal => 150,
@@ -111,10 +349,259 @@ pub fn estimate_tokens(level: CompressionLevel) -> usize {
     }
 }
 
+/// Result of budgeted compression
+#[derive(Debug, Clone)]
+pub struct CompressedFaf {
+    /// The fields that fit
+    pub data: FafData,
+    /// Tokens used by `data` serialized as YAML
+    pub tokens: usize,
+    /// Everything left out, in priority order
+    pub omitted: Vec<Omitted>,
+}
+
+/// A field or list entry left out by budgeted compression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Omitted {
+    /// Dotted path; list items are indexed (`instant_context.key_files[5]`)
+    pub path: String,
+    /// Tokens the entry would have cost
+    pub tokens: usize,
+}
+
+/// One thing budgeted compression can add
+#[derive(Debug, Clone)]
+enum Unit {
+    Field(Vec<String>, Value),
+    Item(Vec<String>, usize, Value),
+    Entry(Vec<String>, Value, Value),
+}
+
+impl Unit {
+    fn path(&self) -> String {
+        match self {
+            Unit::Field(path, _) => path.join("."),
+            Unit::Item(path, index, _) => format!("{}[{}]", path.join("."), index),
+            Unit::Entry(path, key, _) => {
+                format!("{}.{}", path.join("."), key.as_str().unwrap_or_default())
+            }
+        }
+    }
+
+    /// Serialized YAML for this unit on its own
+    fn fragment(&self) -> String {
+        let text = match self {
+            Unit::Field(path, value) => {
+                let mut map = Mapping::new();
+                map.insert(Value::from(path.last().unwrap().as_str()), value.clone());
+                serde_yaml::to_string(&map)
+            }
+            Unit::Item(_, _, value) => serde_yaml::to_string(&vec![value.clone()]),
+            Unit::Entry(_, key, value) => {
+                let mut map = Mapping::new();
+                map.insert(key.clone(), value.clone());
+                serde_yaml::to_string(&map)
+            }
+        };
+        text.unwrap_or_default()
+    }
+
+    /// Container keys (`instant_context:`) this unit needs
+    fn containers(&self) -> Vec<String> {
+        let path = match self {
+            Unit::Field(path, _) => &path[..path.len() - 1],
+            Unit::Item(path, _, _) | Unit::Entry(path, _, _) => &path[..],
+        };
+        (1..=path.len()).map(|n| path[..n].join(".")).collect()
+    }
+
+    fn apply(&self, root: &mut Value) {
+        match self {
+            Unit::Field(path, value) => {
+                let (last, parents) = path.split_last().unwrap();
+                let map = container(root, parents, Value::Mapping(Mapping::new()));
+                if let Value::Mapping(map) = map {
+                    map.insert(Value::from(last.as_str()), value.clone());
+                }
+            }
+            Unit::Item(path, _, value) => {
+                if let Value::Sequence(seq) = container(root, path, Value::Sequence(Vec::new())) {
+                    seq.push(value.clone());
+                }
+            }
+            Unit::Entry(path, key, value) => {
+                if let Value::Mapping(map) = container(root, path, Value::Mapping(Mapping::new())) {
+                    map.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Get or create the container at `path`
+fn container<'a>(root: &'a mut Value, path: &[String], empty: Value) -> &'a mut Value {
+    let mut current = root;
+    for (i, key) in path.iter().enumerate() {
+        let fresh = if i + 1 == path.len() {
+            empty.clone()
+        } else {
+            Value::Mapping(Mapping::new())
+        };
+        let Value::Mapping(map) = current else {
+            return current;
+        };
+        current = map.entry(Value::from(key.as_str())).or_insert(fresh);
+    }
+    current
+}
+
+fn lookup<'a>(root: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(root, |value, key| value.as_mapping()?.get(key.as_str()))
+}
+
+/// Compress FAF to fit a token budget
+///
+/// Fields are added in priority order (goal, what_building, tech_stack,
+/// key_files, commands, stack, ... tags last) until the first one that
+/// does not fit; everything after it is left out, even if it is smaller.
+/// Lists and maps are trimmed entry by entry, so a long `key_files` keeps
+/// its first files. `faf_version` and `project.name` are always kept, even if they
+/// alone exceed the budget.
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{compress_to_budget, parse, HeuristicTokenizer};
+///
+/// let faf = parse(r#"
+/// faf_version: 2.5.0
+/// project:
+///   name: test
+///   goal: Testing
+/// instant_context:
+///   tech_stack: Rust
+///   key_files: [a.rs, b.rs, c.rs]
+/// "#).unwrap();
+///
+/// let compressed = compress_to_budget(&faf, 30, &HeuristicTokenizer::default());
+/// assert!(compressed.tokens <= 30);
+/// for omitted in &compressed.omitted {
+///     println!("left out {} ({} tokens)", omitted.path, omitted.tokens);
+/// }
+/// ```
+pub fn compress_to_budget(
+    faf: &FafFile,
+    budget: usize,
+    tokenizer: &dyn Tokenizer,
+) -> CompressedFaf {
+    let source = serde_yaml::to_value(&faf.data).unwrap_or(Value::Null);
+    let skeleton = {
+        let mut data = compress_minimal(faf);
+        data.project.goal = None;
+        data.instant_context = None;
+        serde_yaml::to_value(&data).unwrap_or(Value::Null)
+    };
+
+    let mut units = Vec::new();
+    for path in BUDGET_PRIORITY {
+        let path: Vec<String> = path.split('.').map(str::to_string).collect();
+        match lookup(&source, &path) {
+            None | Some(Value::Null) => {}
+            Some(Value::Sequence(items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    units.push(Unit::Item(path.clone(), i, item.clone()));
+                }
+            }
+            Some(Value::Mapping(map)) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by_key(|(k, _)| k.as_str().map(str::to_string));
+                for (key, value) in entries {
+                    units.push(Unit::Entry(path.clone(), key.clone(), value.clone()));
+                }
+            }
+            Some(value) => units.push(Unit::Field(path, value.clone())),
+        }
+    }
+
+    // Greedy pass on per-unit costs
+    let mut used = tokenizer.count_tokens(&serde_yaml::to_string(&skeleton).unwrap_or_default());
+    let mut opened: Vec<String> = Vec::new();
+    let mut kept = Vec::new();
+    let mut omitted = Vec::new();
+    let mut full = false;
+    for unit in units {
+        let headers: Vec<String> = unit
+            .containers()
+            .into_iter()
+            .filter(|c| !opened.contains(c) && lookup(&skeleton, &split(c)).is_none())
+            .collect();
+        let header_cost: usize = headers
+            .iter()
+            .map(|c| tokenizer.count_tokens(&format!("{}:\n", c.rsplit('.').next().unwrap())))
+            .sum();
+        let cost = tokenizer.count_tokens(&unit.fragment());
+        full = full || used + header_cost + cost > budget;
+        if !full {
+            used += header_cost + cost;
+            opened.extend(headers);
+            kept.push(unit);
+        } else {
+            omitted.push(Omitted {
+                path: unit.path(),
+                tokens: cost,
+            });
+        }
+    }
+
+    // Costs are not exactly additive; drop the lowest-priority units until
+    // the real serialization fits
+    loop {
+        let mut root = skeleton.clone();
+        for unit in &kept {
+            unit.apply(&mut root);
+        }
+        let data: FafData = serde_yaml::from_value(root).unwrap_or_else(|_| compress_minimal(faf));
+        let tokens = tokenizer.count_tokens(&serde_yaml::to_string(&data).unwrap_or_default());
+        if tokens <= budget || kept.is_empty() {
+            omitted.sort_by_key(|o| priority_of(&o.path));
+            return CompressedFaf {
+                data,
+                tokens,
+                omitted,
+            };
+        }
+        let unit = kept.pop().unwrap();
+        omitted.push(Omitted {
+            path: unit.path(),
+            tokens: tokenizer.count_tokens(&unit.fragment()),
+        });
+    }
+}
+
+fn split(path: &str) -> Vec<String> {
+    path.split('.').map(str::to_string).collect()
+}
+
+fn priority_of(path: &str) -> (usize, usize) {
+    let base = path.split('[').next().unwrap_or(path);
+    let rank = BUDGET_PRIORITY
+        .iter()
+        .position(|p| base == *p || base.starts_with(&format!("{}.", p)))
+        .unwrap_or(usize::MAX);
+    let index = path
+        .split('[')
+        .nth(1)
+        .and_then(|i| i.trim_end_matches(']').parse().ok())
+        .unwrap_or(0);
+    (rank, index)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::parse;
+    use crate::tokens::HeuristicTokenizer;
 
     #[test]
     fn test_compress_minimal() {
@@ -175,4 +662,134 @@ human_context:
         // Human context still excluded
         assert!(compressed.human_context.is_none());
     }
+
+    #[test]
+    fn test_compress_to_budget_trims_lists() {
+        let mut content = String::from(
+            r#"
+faf_version: 2.5.0
+project:
+  name: test
+  goal: Testing
+instant_context:
+  what_building: App
+  tech_stack: Rust
+  key_files:
+"#,
+        );
+        for i in 0..50 {
+            content.push_str(&format!("    - src/module_{}.rs\n", i));
+        }
+        content.push_str("stack:\n  backend: Rust\ntags:\n  - rust\n");
+        let faf = parse(&content).unwrap();
+        let tokenizer = HeuristicTokenizer::default();
+
+        let compressed = compress_to_budget(&faf, 120, &tokenizer);
+        assert!(compressed.tokens <= 120);
+        assert_eq!(compressed.data.project.goal.as_deref(), Some("Testing"));
+        let kept = compressed
+            .data
+            .instant_context
+            .as_ref()
+            .unwrap()
+            .key_files
+            .len();
+        assert!(kept > 0 && kept < 50);
+        assert_eq!(
+            compressed.data.instant_context.unwrap().key_files[0],
+            "src/module_0.rs"
+        );
+
+        // Everything not kept is reported, in priority order
+        assert_eq!(
+            compressed.omitted[0].path,
+            format!("instant_context.key_files[{}]", kept)
+        );
+        let dropped_files = compressed
+            .omitted
+            .iter()
+            .filter(|o| o.path.starts_with("instant_context.key_files["))
+            .count();
+        assert_eq!(dropped_files, 50 - kept);
+        // Nothing after the first key file that did not fit, however small
+        assert!(compressed.omitted.iter().any(|o| o.path == "tags[0]"));
+        assert!(compressed.omitted.iter().any(|o| o.path == "stack.backend"));
+    }
+
+    #[test]
+    fn test_compress_to_budget_everything_fits() {
+        let faf = parse(include_str!("../examples/full.faf")).unwrap();
+        let compressed = compress_to_budget(&faf, 100_000, &HeuristicTokenizer::default());
+        assert!(compressed.omitted.is_empty());
+        assert_eq!(
+            serde_yaml::to_value(&compressed.data).unwrap(),
+            serde_yaml::to_value(&faf.data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compress_to_budget_keeps_required() {
+        let faf = parse("faf_version: 2.5.0\nproject:\n  name: test\n  goal: Testing").unwrap();
+        let words = |text: &str| text.split_whitespace().count();
+        let compressed = compress_to_budget(&faf, 1, &words);
+        assert_eq!(compressed.data.project.name, "test");
+        assert!(compressed.data.project.goal.is_none());
+        assert_eq!(compressed.omitted[0].path, "project.goal");
+    }
+
+    #[test]
+    fn test_compress_custom_profile() {
+        let faf = parse(include_str!("../examples/full.faf")).unwrap();
+        // Code-review agent: commands and preferences, no human_context
+        let profile = CompressionProfile::from_yaml(
+            r#"
+include:
+  - project.goal
+  - instant_context.commands
+  - instant_context.key_files
+  - preferences
+exclude:
+  - instant_context.commands.dev
+caps:
+  instant_context.key_files: 2
+"#,
+        )
+        .unwrap();
+        let compressed = compress_with_profile(&faf, &profile);
+
+        assert_eq!(compressed.faf_version, "2.5.0");
+        assert_eq!(compressed.project.name, "full-example");
+        assert!(compressed.project.goal.is_some());
+        assert!(compressed.project.license.is_none());
+        let ic = compressed.instant_context.unwrap();
+        assert!(ic.tech_stack.is_none());
+        assert_eq!(ic.key_files, vec!["src/index.ts", "src/app.tsx"]);
+        assert_eq!(ic.commands.len(), 3);
+        assert!(!ic.commands.contains_key("dev"));
+        assert!(compressed.preferences.is_some());
+        assert!(compressed.human_context.is_none());
+        assert!(compressed.stack.is_none());
+    }
+
+    #[test]
+    fn test_compress_profile_builder() {
+        let faf = parse(include_str!("../examples/full.faf")).unwrap();
+        let profile = CompressionProfile::new()
+            .exclude("human_context.why")
+            .exclude("project")
+            .cap("tags", 1)
+            .cap("instant_context.commands", 2);
+        let compressed = profile.apply(&faf.data);
+
+        // Required fields survive an exclude of their section
+        assert_eq!(compressed.project.name, "full-example");
+        assert!(compressed.project.goal.is_none());
+        assert!(compressed.human_context.as_ref().unwrap().why_field.is_none());
+        assert!(compressed.human_context.as_ref().unwrap().who.is_some());
+        assert_eq!(compressed.tags, vec!["typescript"]);
+        let commands = compressed.instant_context.unwrap().commands;
+        // Map caps keep the first keys in sorted order
+        assert!(commands.contains_key("build") && commands.contains_key("dev"));
+        assert_eq!(commands.len(), 2);
+    }
 }
@@ -0,0 +1,316 @@
+//! Structural diff between two FAF documents
+
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+use std::fmt;
+
+use crate::parser::FafError;
+use crate::types::FafData;
+
+/// One field-level change
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Change {
+    /// Field present only in the new document
+    Added { path: String, value: Value },
+    /// Field present only in the old document
+    Removed { path: String, value: Value },
+    /// Field with a different value
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+    /// List compared as a set (`tags`, `key_files`)
+    ListChanged {
+        path: String,
+        added: Vec<Value>,
+        removed: Vec<Value>,
+    },
+}
+
+impl Change {
+    /// Dotted path using YAML names (`instant_context.key_files`)
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Changed { path, .. }
+            | Change::ListChanged { path, .. } => path,
+        }
+    }
+}
+
+/// Changes between two documents, sorted by path
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FafDiff {
+    pub changes: Vec<Change>,
+}
+
+impl FafDiff {
+    /// True if the documents are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Change at `path`, if any
+    pub fn get(&self, path: &str) -> Option<&Change> {
+        self.changes.iter().find(|c| c.path() == path)
+    }
+
+    /// One-line summary for PR comments, keyed by field name
+    ///
+    /// `tech_stack: Rust → Rust, Python; +3 key_files; ai_score: 80% → 85%`
+    pub fn summary(&self) -> String {
+        self.changes
+            .iter()
+            .map(|change| {
+                let path = change.path();
+                let name = path.rsplit('.').next().unwrap_or(path);
+                match change {
+                    Change::Added { .. } => format!("+{}", name),
+                    Change::Removed { .. } => format!("-{}", name),
+                    Change::Changed { old, new, .. } => {
+                        format!("{}: {} → {}", name, display(old), display(new))
+                    }
+                    Change::ListChanged { added, removed, .. } => {
+                        let mut counts = Vec::new();
+                        if !added.is_empty() {
+                            counts.push(format!("+{}", added.len()));
+                        }
+                        if !removed.is_empty() {
+                            counts.push(format!("-{}", removed.len()));
+                        }
+                        format!("{} {}", counts.join(" "), name)
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// Changes as a JSON document
+    ///
+    /// ```json
+    /// {"changes": [{"op": "changed", "path": "ai_score", "old": "80%", "new": "85%"}]}
+    /// ```
+    pub fn to_json(&self) -> Result<String, FafError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// One line per change: `+` added, `-` removed, `~` changed
+impl fmt::Display for FafDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            match change {
+                Change::Added { path, value } => writeln!(f, "+ {}: {}", path, display(value))?,
+                Change::Removed { path, value } => writeln!(f, "- {}: {}", path, display(value))?,
+                Change::Changed { path, old, new } => {
+                    writeln!(f, "~ {}: {} → {}", path, display(old), display(new))?
+                }
+                Change::ListChanged {
+                    path,
+                    added,
+                    removed,
+                } => {
+                    let items: Vec<String> = added
+                        .iter()
+                        .map(|v| format!("+{}", display(v)))
+                        .chain(removed.iter().map(|v| format!("-{}", display(v))))
+                        .collect();
+                    writeln!(f, "~ {}: {}", path, items.join(", "))?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        other => serde_json::to_string(other).unwrap_or_default(),
+    }
+}
+
+/// Field-level changes from `old` to `new`
+///
+/// Sections are compared field by field, lists as sets (reordering is not
+/// a change).
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{diff, parse};
+///
+/// let old = parse("faf_version: 2.5.0\nai_score: 80%\nproject:\n  name: app\ntags: [rust]").unwrap();
+/// let new = parse("faf_version: 2.5.0\nai_score: 85%\nproject:\n  name: app\ntags: [rust, cli, sdk]").unwrap();
+///
+/// let changes = diff(&old.data, &new.data);
+/// assert_eq!(changes.summary(), "ai_score: 80% → 85%; +2 tags");
+/// ```
+pub fn diff(old: &FafData, new: &FafData) -> FafDiff {
+    let old = serde_yaml::to_value(old).unwrap_or(Value::Null);
+    let new = serde_yaml::to_value(new).unwrap_or(Value::Null);
+    let mut changes = Vec::new();
+    walk("", Some(&old), Some(&new), &mut changes);
+    changes.sort_by(|a, b| a.path().cmp(b.path()));
+    FafDiff { changes }
+}
+
+fn walk(path: &str, old: Option<&Value>, new: Option<&Value>, out: &mut Vec<Change>) {
+    let old = old.filter(|v| !v.is_null());
+    let new = new.filter(|v| !v.is_null());
+    if old == new {
+        return;
+    }
+
+    let empty_map = Mapping::new();
+    match (old, new) {
+        (Some(Value::Mapping(_)) | None, Some(Value::Mapping(_)) | None) => {
+            let o = old.and_then(Value::as_mapping).unwrap_or(&empty_map);
+            let n = new.and_then(Value::as_mapping).unwrap_or(&empty_map);
+            for key in o.keys().chain(n.keys().filter(|k| !o.contains_key(*k))) {
+                if let Some(name) = key.as_str() {
+                    let child = if path.is_empty() {
+                        name.to_string()
+                    } else {
+                        format!("{}.{}", path, name)
+                    };
+                    walk(&child, o.get(key), n.get(key), out);
+                }
+            }
+        }
+        (Some(Value::Sequence(_)) | None, Some(Value::Sequence(_)) | None) => {
+            let o = old
+                .and_then(Value::as_sequence)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let n = new
+                .and_then(Value::as_sequence)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let added: Vec<Value> = n.iter().filter(|v| !o.contains(v)).cloned().collect();
+            let removed: Vec<Value> = o.iter().filter(|v| !n.contains(v)).cloned().collect();
+            if !added.is_empty() || !removed.is_empty() {
+                out.push(Change::ListChanged {
+                    path: path.to_string(),
+                    added,
+                    removed,
+                });
+            }
+        }
+        (None, Some(value)) => out.push(Change::Added {
+            path: path.to_string(),
+            value: value.clone(),
+        }),
+        (Some(value), None) => out.push(Change::Removed {
+            path: path.to_string(),
+            value: value.clone(),
+        }),
+        (Some(old), Some(new)) => out.push(Change::Changed {
+            path: path.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const OLD: &str = r#"
+faf_version: 2.5.0
+ai_score: 80%
+project:
+  name: app
+  goal: Old goal
+instant_context:
+  tech_stack: Rust
+  key_files: [src/lib.rs, src/old.rs]
+  commands:
+    test: cargo test
+human_context:
+  why: Because
+"#;
+
+    const NEW: &str = r#"
+faf_version: 2.5.0
+ai_score: 85%
+project:
+  name: app
+  goal: Old goal
+instant_context:
+  tech_stack: Rust, Python
+  key_files: [src/new.rs, src/lib.rs]
+  commands:
+    test: cargo test
+    lint: cargo clippy
+stack:
+  database: PostgreSQL
+"#;
+
+    #[test]
+    fn test_diff_fields_and_sets() {
+        let changes = diff(&parse(OLD).unwrap().data, &parse(NEW).unwrap().data);
+        let paths: Vec<_> = changes.changes.iter().map(Change::path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "ai_score",
+                "human_context.why",
+                "instant_context.commands.lint",
+                "instant_context.key_files",
+                "instant_context.tech_stack",
+                "stack.database",
+            ]
+        );
+        assert_eq!(
+            changes.get("instant_context.key_files"),
+            Some(&Change::ListChanged {
+                path: "instant_context.key_files".into(),
+                added: vec![Value::from("src/new.rs")],
+                removed: vec![Value::from("src/old.rs")],
+            })
+        );
+        assert_eq!(
+            changes.summary(),
+            "ai_score: 80% → 85%; -why; +lint; +1 -1 key_files; tech_stack: Rust → Rust, Python; +database"
+        );
+        assert_eq!(
+            changes.to_string().lines().nth(3),
+            Some("~ instant_context.key_files: +src/new.rs, -src/old.rs")
+        );
+    }
+
+    #[test]
+    fn test_diff_identical_and_reordered() {
+        let a = parse(OLD).unwrap().data;
+        let b = parse(&OLD.replace("[src/lib.rs, src/old.rs]", "[src/old.rs, src/lib.rs]"))
+            .unwrap()
+            .data;
+        assert!(diff(&a, &b).is_empty());
+        assert_eq!(diff(&a, &b).to_string(), "");
+    }
+
+    #[test]
+    fn test_diff_json() {
+        let old = parse("faf_version: 2.5.0\nproject:\n  name: a").unwrap();
+        let new = parse("faf_version: 2.5.0\nproject:\n  name: b\ntags: [x]").unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&diff(&old.data, &new.data).to_json().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"changes": [
+                {"op": "changed", "path": "project.name", "old": "a", "new": "b"},
+                {"op": "list_changed", "path": "tags", "added": ["x"], "removed": []}
+            ]})
+        );
+    }
+}
@@ -1,13 +1,18 @@
 //! FAF file discovery - find project.faf in directory tree
 
+use ignore::WalkBuilder;
+use std::collections::BTreeMap;
 use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 
 /// Maximum directories to traverse upward
-const MAX_DEPTH: usize = 10;
+pub(crate) const MAX_DEPTH: usize = 10;
 
 /// FAF file names to search for (in priority order)
-const FAF_FILES: &[&str] = &["project.faf", ".faf"];
+pub(crate) const FAF_FILES: &[&str] = &["project.faf", ".faf"];
 
 /// Find FAF file starting from given directory, walking up to parents
 ///
@@ -31,34 +36,363 @@ const FAF_FILES: &[&str] = &["project.faf", ".faf"];
 /// }
 /// ```
 pub fn find_faf_file<P: AsRef<Path>>(start_dir: Option<P>) -> Option<PathBuf> {
-    let start = match start_dir {
-        Some(p) => p.as_ref().to_path_buf(),
-        None => env::current_dir().ok()?,
-    };
+    find_faf_file_with(start_dir, &DiscoveryOptions::default()).map(|m| m.path)
+}
+
+/// Environment variable conventionally used with
+/// [`DiscoveryOptions::env_override`]
+pub const FAF_PATH_ENV: &str = "FAF_PATH";
+
+/// Where an upward search stops
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StopMarker {
+    /// Directory containing an entry with this name (`.git`)
+    Entry(String),
+    /// Directory whose Cargo.toml has a `[workspace]` table
+    CargoWorkspace,
+}
+
+impl StopMarker {
+    fn matches(&self, dir: &Path) -> bool {
+        match self {
+            StopMarker::Entry(name) => dir.join(name).exists(),
+            StopMarker::CargoWorkspace => fs::read_to_string(dir.join("Cargo.toml"))
+                .ok()
+                .and_then(|content| content.parse::<toml::Table>().ok())
+                .is_some_and(|manifest| manifest.contains_key("workspace")),
+        }
+    }
+}
+
+/// Settings for [`find_faf_file_with`]
+///
+/// The default matches [`find_faf_file`]: `project.faf` then `.faf`, up to
+/// 10 directories, no stop markers and no environment override.
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{DiscoveryOptions, FAF_PATH_ENV};
+///
+/// let options = DiscoveryOptions::new()
+///     .max_depth(20)
+///     .stop_at_repo_root()
+///     .stop_at_cargo_workspace()
+///     .candidate(".faf.yaml")
+///     .candidate("*.faf")
+///     .env_override(FAF_PATH_ENV);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryOptions {
+    max_depth: usize,
+    candidates: Vec<String>,
+    stop_markers: Vec<StopMarker>,
+    env_var: Option<String>,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        DiscoveryOptions {
+            max_depth: MAX_DEPTH,
+            candidates: FAF_FILES.iter().map(|f| f.to_string()).collect(),
+            stop_markers: Vec::new(),
+            env_var: None,
+        }
+    }
+}
+
+impl DiscoveryOptions {
+    /// Default options
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    let mut current = start.as_path();
-    let mut depth = 0;
+    /// Number of directories to check, starting directory included
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Add a file name or glob pattern (`*.faf`), after existing candidates
+    pub fn candidate(mut self, name: impl Into<String>) -> Self {
+        self.candidates.push(name.into());
+        self
+    }
 
-    while depth < MAX_DEPTH {
-        // Check for FAF files in priority order
-        for &filename in FAF_FILES {
-            let candidate = current.join(filename);
-            if candidate.is_file() {
-                return Some(candidate);
+    /// Stop after the first directory matching `marker`
+    pub fn stop_at(mut self, marker: StopMarker) -> Self {
+        self.stop_markers.push(marker);
+        self
+    }
+
+    /// Stop at the repository root (directory containing `.git`)
+    pub fn stop_at_repo_root(self) -> Self {
+        self.stop_at(StopMarker::Entry(".git".to_string()))
+    }
+
+    /// Stop at the Cargo workspace root
+    pub fn stop_at_cargo_workspace(self) -> Self {
+        self.stop_at(StopMarker::CargoWorkspace)
+    }
+
+    /// Let an environment variable name the file to use
+    ///
+    /// If the variable names a file it is used as-is; a directory becomes
+    /// the starting directory. A path that does not exist finds nothing.
+    pub fn env_override(mut self, var: impl Into<String>) -> Self {
+        self.env_var = Some(var.into());
+        self
+    }
+
+    /// Best match in each directory from `start` up to the first stop
+    /// marker, nearest first; just the nearest if no marker is reached
+    pub(crate) fn layers_from(
+        &self,
+        start: &Path,
+        unreadable: &mut Vec<Unreadable>,
+    ) -> Vec<PathBuf> {
+        let mut layers = Vec::new();
+        for dir in start.ancestors().take(self.max_depth) {
+            if let Some((path, _)) = self.matches_in(dir, unreadable).into_iter().next() {
+                layers.push(path);
+            }
+            if self.stop_markers.iter().any(|m| m.matches(dir)) {
+                return layers;
             }
         }
+        layers.truncate(1);
+        layers
+    }
 
-        // Move to parent directory
-        match current.parent() {
-            Some(parent) if parent != current => {
-                current = parent;
-                depth += 1;
+    /// Files in `dir` matching a candidate, best first, with the rule
+    fn matches_in(&self, dir: &Path, unreadable: &mut Vec<Unreadable>) -> Vec<(PathBuf, String)> {
+        let mut found = Vec::new();
+        for candidate in &self.candidates {
+            if !candidate.contains(['*', '?', '[']) {
+                let path = dir.join(candidate);
+                if is_file(&path, unreadable) {
+                    found.push((path, candidate.clone()));
+                }
+                continue;
+            }
+            let Ok(pattern) = glob::Pattern::new(candidate) else {
+                continue;
+            };
+            let entries = match fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    note_error(dir, e, unreadable);
+                    continue;
+                }
+            };
+            let mut paths: Vec<PathBuf> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|p| {
+                    p.file_name()
+                        .is_some_and(|n| pattern.matches(&n.to_string_lossy()))
+                })
+                .collect();
+            paths.sort();
+            for path in paths {
+                if !found.iter().any(|(p, _)| *p == path) && is_file(&path, unreadable) {
+                    found.push((path, candidate.clone()));
+                }
             }
-            _ => break,
         }
+        found
     }
+}
 
-    None
+/// `Path::is_file`, but recording errors other than "not there"
+fn is_file(path: &Path, unreadable: &mut Vec<Unreadable>) -> bool {
+    match fs::metadata(path) {
+        Ok(meta) => meta.is_file(),
+        Err(e) => {
+            note_error(path, e, unreadable);
+            false
+        }
+    }
+}
+
+fn note_error(path: &Path, error: io::Error, unreadable: &mut Vec<Unreadable>) {
+    if !matches!(
+        error.kind(),
+        io::ErrorKind::NotFound | io::ErrorKind::NotADirectory
+    ) && !unreadable.iter().any(|u| u.path == path)
+    {
+        unreadable.push(Unreadable {
+            path: path.to_path_buf(),
+            kind: error.kind(),
+        });
+    }
+}
+
+/// Why a file was chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchRule {
+    /// Named by this environment variable
+    EnvOverride(String),
+    /// Matched this candidate name or pattern
+    Candidate(String),
+}
+
+/// A file found by [`find_faf_file_with`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FafMatch {
+    pub path: PathBuf,
+    pub rule: MatchRule,
+    /// Directories above the starting directory (0 = found in it)
+    pub depth: usize,
+}
+
+/// Find a FAF file walking upward, with configurable rules
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{find_faf_file_with, DiscoveryOptions};
+/// use std::path::PathBuf;
+///
+/// let options = DiscoveryOptions::new().stop_at_repo_root();
+/// if let Some(found) = find_faf_file_with::<PathBuf>(None, &options) {
+///     println!("{} ({:?})", found.path.display(), found.rule);
+/// }
+/// ```
+pub fn find_faf_file_with<P: AsRef<Path>>(
+    start_dir: Option<P>,
+    options: &DiscoveryOptions,
+) -> Option<FafMatch> {
+    find_faf_file_detailed(start_dir, options).found
+}
+
+/// An entry discovery could not inspect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unreadable {
+    pub path: PathBuf,
+    pub kind: io::ErrorKind,
+}
+
+/// Everything an upward search saw
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryReport {
+    /// The file [`find_faf_file_with`] returns
+    pub found: Option<FafMatch>,
+    /// Every matching file seen, best first
+    pub candidates: Vec<PathBuf>,
+    /// Other matches in the directory `found` came from - a stale `.faf`
+    /// next to `project.faf`, for example
+    pub shadowed: Vec<PathBuf>,
+    /// Entries that exist but could not be checked (permissions, symlink
+    /// loops); one of these may be the file that was meant
+    pub unreadable: Vec<Unreadable>,
+    /// Directories searched, nearest first
+    pub visited: Vec<PathBuf>,
+}
+
+impl DiscoveryReport {
+    /// True if more than one file could have been meant
+    pub fn is_ambiguous(&self) -> bool {
+        !self.shadowed.is_empty()
+    }
+
+    /// The match, failing on unreadable entries and ambiguity
+    pub fn into_result(self) -> Result<FafMatch, FindError> {
+        if let Some(entry) = self.unreadable.into_iter().next() {
+            return Err(FindError::Unreadable(entry.path, io::Error::from(entry.kind)));
+        }
+        let found = self.found.ok_or(FindError::NotFound)?;
+        if !self.shadowed.is_empty() {
+            return Err(FindError::Ambiguous {
+                found: found.path,
+                shadowed: self.shadowed,
+            });
+        }
+        Ok(found)
+    }
+}
+
+/// Search like [`find_faf_file_with`] and report everything seen on the way
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{find_faf_file_detailed, DiscoveryOptions};
+/// use std::path::PathBuf;
+///
+/// let report = find_faf_file_detailed::<PathBuf>(None, &DiscoveryOptions::default());
+/// for stale in &report.shadowed {
+///     eprintln!("warning: {} is ignored", stale.display());
+/// }
+/// let found = report.into_result()?;
+/// # Ok::<(), faf_rust_sdk::FindError>(())
+/// ```
+pub fn find_faf_file_detailed<P: AsRef<Path>>(
+    start_dir: Option<P>,
+    options: &DiscoveryOptions,
+) -> DiscoveryReport {
+    search(start_dir.map(|p| p.as_ref().to_path_buf()), options, |var| {
+        env::var_os(var)
+    })
+}
+
+/// [`find_faf_file_detailed`] reading the environment through `var_os`
+fn search(
+    start_dir: Option<PathBuf>,
+    options: &DiscoveryOptions,
+    var_os: impl Fn(&str) -> Option<OsString>,
+) -> DiscoveryReport {
+    let mut report = DiscoveryReport::default();
+    let mut start = match start_dir {
+        Some(p) => p,
+        None => match env::current_dir() {
+            Ok(dir) => dir,
+            Err(e) => {
+                note_error(Path::new("."), e, &mut report.unreadable);
+                return report;
+            }
+        },
+    };
+
+    if let Some(var) = &options.env_var {
+        if let Some(value) = var_os(var).filter(|v| !v.is_empty()) {
+            let path = PathBuf::from(value);
+            if is_file(&path, &mut report.unreadable) {
+                report.candidates.push(path.clone());
+                report.found = Some(FafMatch {
+                    path,
+                    rule: MatchRule::EnvOverride(var.clone()),
+                    depth: 0,
+                });
+                return report;
+            }
+            if !path.is_dir() {
+                return report;
+            }
+            start = path;
+        }
+    }
+
+    for (depth, dir) in start.ancestors().take(options.max_depth).enumerate() {
+        report.visited.push(dir.to_path_buf());
+        let mut matches = options.matches_in(dir, &mut report.unreadable).into_iter();
+        if let Some((path, candidate)) = matches.next() {
+            report.candidates.push(path.clone());
+            report.shadowed = matches.map(|(p, _)| p).collect();
+            report.candidates.extend(report.shadowed.iter().cloned());
+            report.found = Some(FafMatch {
+                path,
+                rule: MatchRule::Candidate(candidate),
+                depth,
+            });
+            break;
+        }
+        if options.stop_markers.iter().any(|m| m.matches(dir)) {
+            break;
+        }
+    }
+
+    report
 }
 
 /// Find and parse FAF file in one call
@@ -83,13 +417,127 @@ pub fn find_and_parse<P: AsRef<Path>>(
     crate::parser::parse_file(&path).map_err(FindError::ParseError)
 }
 
+/// Options for [`discover_all`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoverOptions {
+    /// Directory levels below the root to search (default 10)
+    pub max_depth: usize,
+    /// Honour `.gitignore`, `.git/info/exclude` and `.fafignore` (default true)
+    pub respect_ignore_files: bool,
+    /// Directory names never entered
+    pub skip_dirs: Vec<String>,
+}
+
+impl Default for DiscoverOptions {
+    fn default() -> Self {
+        DiscoverOptions {
+            max_depth: MAX_DEPTH,
+            respect_ignore_files: true,
+            skip_dirs: ["target", "node_modules", ".git"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+/// A FAF file found by [`discover_all`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FafLocation {
+    /// Path to the file
+    pub path: PathBuf,
+    /// Package directory relative to the root (empty for the root itself)
+    pub package: PathBuf,
+}
+
+/// Find every FAF file beneath `root`, one per package directory
+///
+/// Walks downward, skipping ignored paths and `options.skip_dirs`. Where a
+/// directory has both, `project.faf` is reported over `.faf`. Results are
+/// sorted by package path.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{discover_all, DiscoverOptions};
+///
+/// for location in discover_all(".", &DiscoverOptions::default()) {
+///     println!("{}: {}", location.package.display(), location.path.display());
+/// }
+/// ```
+pub fn discover_all<P: AsRef<Path>>(root: P, options: &DiscoverOptions) -> Vec<FafLocation> {
+    let root = root.as_ref();
+    let respect = options.respect_ignore_files;
+    let skip_dirs = options.skip_dirs.clone();
+
+    let mut walker = WalkBuilder::new(root);
+    walker
+        .max_depth(Some(options.max_depth + 1))
+        .hidden(false)
+        .parents(respect)
+        .ignore(respect)
+        .git_ignore(respect)
+        .git_exclude(respect)
+        .git_global(false)
+        .require_git(false)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .filter_entry(move |entry| {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0 || !is_dir || !skip_dirs.iter().any(|d| *d == name)
+        });
+    if respect {
+        walker.add_custom_ignore_filename(".fafignore");
+    }
+
+    // Best match per directory: (priority, path)
+    let mut found: BTreeMap<PathBuf, (usize, PathBuf)> = BTreeMap::new();
+    for entry in walker.build().flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy();
+        let Some(priority) = FAF_FILES.iter().position(|f| *f == name) else {
+            continue;
+        };
+        let path = entry.into_path();
+        let package = path
+            .parent()
+            .and_then(|dir| dir.strip_prefix(root).ok())
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        match found.get(&package) {
+            Some((best, _)) if *best <= priority => {}
+            _ => {
+                found.insert(package, (priority, path));
+            }
+        }
+    }
+
+    found
+        .into_iter()
+        .map(|(package, (_, path))| FafLocation { path, package })
+        .collect()
+}
+
 /// Errors from find operations
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum FindError {
     /// No FAF file found in directory tree
     NotFound,
     /// FAF file found but failed to parse
     ParseError(crate::parser::FafError),
+    /// `extends` chain leads back to a file already in it (first file repeated last)
+    ExtendsCycle(Vec<PathBuf>),
+    /// File named by `extends` does not exist
+    ExtendsNotFound(PathBuf),
+    /// More than one candidate in the same directory
+    Ambiguous {
+        found: PathBuf,
+        shadowed: Vec<PathBuf>,
+    },
+    /// An entry on the search path could not be checked
+    Unreadable(PathBuf, io::Error),
 }
 
 impl std::fmt::Display for FindError {
@@ -97,6 +545,23 @@ impl std::fmt::Display for FindError {
         match self {
             FindError::NotFound => write!(f, "No FAF file found in directory tree"),
             FindError::ParseError(e) => write!(f, "Parse error: {}", e),
+            FindError::ExtendsCycle(chain) => {
+                let chain: Vec<_> = chain.iter().map(|p| p.display().to_string()).collect();
+                write!(f, "Cycle in extends: {}", chain.join(" -> "))
+            }
+            FindError::ExtendsNotFound(path) => {
+                write!(f, "Extended file not found: {}", path.display())
+            }
+            FindError::Ambiguous { found, shadowed } => {
+                let shadowed: Vec<_> = shadowed.iter().map(|p| p.display().to_string()).collect();
+                write!(
+                    f,
+                    "Ambiguous FAF files: {} shadows {}",
+                    found.display(),
+                    shadowed.join(", ")
+                )
+            }
+            FindError::Unreadable(path, e) => write!(f, "Cannot read {}: {}", path.display(), e),
         }
     }
 }
@@ -204,5 +669,179 @@ mod tests {
         // Should NOT find it (too deep)
         let found = find_faf_file(Some(&deep));
         assert!(found.is_none());
+
+        // ...unless the limit is raised
+        let options = DiscoveryOptions::new().max_depth(16);
+        let found = find_faf_file_with(Some(&deep), &options).unwrap();
+        assert_eq!(found.path, faf_path);
+        assert_eq!(found.depth, 15);
+        assert_eq!(found.rule, MatchRule::Candidate("project.faf".into()));
+    }
+
+    #[test]
+    fn test_stop_markers() {
+        let home = TempDir::new().unwrap();
+        fs::write(home.path().join("project.faf"), "faf_version: 2.5.0").unwrap();
+        let repo = home.path().join("repo");
+        let member = repo.join("crates/core");
+        fs::create_dir_all(&member).unwrap();
+
+        // No boundary: the stray file above the repository is picked up
+        assert!(find_faf_file(Some(&member)).is_some());
+
+        fs::create_dir(repo.join(".git")).unwrap();
+        let options = DiscoveryOptions::new().stop_at_repo_root();
+        assert_eq!(find_faf_file_with(Some(&member), &options), None);
+
+        fs::write(repo.join("crates/Cargo.toml"), "[workspace]\nmembers = [\"core\"]").unwrap();
+        fs::write(repo.join("project.faf"), "faf_version: 2.5.0").unwrap();
+        let options = DiscoveryOptions::new().stop_at_cargo_workspace();
+        assert_eq!(find_faf_file_with(Some(&member), &options), None);
+        let options = DiscoveryOptions::new().stop_at_repo_root();
+        assert_eq!(
+            find_faf_file_with(Some(&member), &options).unwrap().path,
+            repo.join("project.faf")
+        );
+    }
+
+    #[test]
+    fn test_custom_candidates_and_env_override() {
+        let dir = TempDir::new().unwrap();
+        let child = dir.path().join("child");
+        fs::create_dir(&child).unwrap();
+        fs::write(dir.path().join("b.faf"), "faf_version: 2.5.0").unwrap();
+        fs::write(dir.path().join("a.faf"), "faf_version: 2.5.0").unwrap();
+        fs::write(child.join(".faf.yaml"), "faf_version: 2.5.0").unwrap();
+
+        let options = DiscoveryOptions::new().candidate("*.faf");
+        let found = find_faf_file_with(Some(&child), &options).unwrap();
+        assert_eq!(found.path, dir.path().join("a.faf"));
+        assert_eq!(found.rule, MatchRule::Candidate("*.faf".into()));
+
+        let options = options.candidate(".faf.yaml");
+        let found = find_faf_file_with(Some(&child), &options).unwrap();
+        assert_eq!(found.path, child.join(".faf.yaml"));
+        assert_eq!(found.depth, 0);
+
+        // The environment is read through a parameter: setting real
+        // variables races with other tests reading them
+        let var = "FAF_PATH_DISCOVERY_TEST";
+        let options = DiscoveryOptions::new().env_override(var);
+        let with_env = |value: Option<PathBuf>| {
+            search(Some(child.clone()), &options, move |name| {
+                assert_eq!(name, var);
+                value.clone().map(OsString::from)
+            })
+            .found
+        };
+        let found = with_env(Some(dir.path().join("b.faf"))).unwrap();
+        assert_eq!(found.path, dir.path().join("b.faf"));
+        assert_eq!(found.rule, MatchRule::EnvOverride(var.into()));
+
+        assert_eq!(with_env(Some(dir.path().join("missing.faf"))), None);
+        assert_eq!(with_env(None), None);
+    }
+
+    #[test]
+    fn test_discover_all_packages() {
+        let root = TempDir::new().unwrap();
+        let content = "faf_version: 2.5.0\nproject:\n  name: test";
+        for file in [
+            "project.faf",
+            "packages/api/project.faf",
+            "packages/web/.faf",
+            "packages/both/project.faf",
+            "packages/both/.faf",
+            "target/debug/project.faf",
+            "node_modules/dep/project.faf",
+            "generated/project.faf",
+            "vendor/lib/project.faf",
+            "a/b/c/project.faf",
+        ] {
+            let path = root.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        fs::write(root.path().join(".gitignore"), "generated/\n").unwrap();
+        fs::write(root.path().join(".fafignore"), "vendor/\n").unwrap();
+
+        let packages = |options: &DiscoverOptions| {
+            discover_all(root.path(), options)
+                .into_iter()
+                .map(|l| l.package.to_string_lossy().replace('\\', "/"))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            packages(&DiscoverOptions::default()),
+            ["", "a/b/c", "packages/api", "packages/both", "packages/web"]
+        );
+
+        let found = discover_all(root.path(), &DiscoverOptions::default());
+        assert_eq!(found[0].path, root.path().join("project.faf"));
+        assert_eq!(found[3].path, root.path().join("packages/both/project.faf"));
+        assert_eq!(found[4].path, root.path().join("packages/web/.faf"));
+
+        let shallow = DiscoverOptions {
+            max_depth: 2,
+            ..Default::default()
+        };
+        assert!(!packages(&shallow).contains(&"a/b/c".to_string()));
+        assert!(packages(&shallow).contains(&"packages/api".to_string()));
+
+        let everything = DiscoverOptions {
+            respect_ignore_files: false,
+            ..Default::default()
+        };
+        assert!(packages(&everything).contains(&"generated".to_string()));
+        assert!(packages(&everything).contains(&"vendor/lib".to_string()));
+        assert!(!packages(&everything).contains(&"target/debug".to_string()));
+    }
+
+    #[test]
+    fn test_detailed_report_ambiguity() {
+        let dir = TempDir::new().unwrap();
+        let child = dir.path().join("child");
+        fs::create_dir(&child).unwrap();
+        let modern = dir.path().join("project.faf");
+        let legacy = dir.path().join(".faf");
+        fs::write(&modern, "faf_version: 2.5.0").unwrap();
+        fs::write(&legacy, "faf_version: 2.5.0").unwrap();
+
+        let report = find_faf_file_detailed(Some(&child), &DiscoveryOptions::default());
+        assert_eq!(report.visited, [child.clone(), dir.path().to_path_buf()]);
+        assert_eq!(report.candidates, [modern.clone(), legacy.clone()]);
+        assert_eq!(report.shadowed, vec![legacy.clone()]);
+        assert!(report.is_ambiguous());
+        assert_eq!(report.found.as_ref().unwrap().path, modern);
+        match report.into_result() {
+            Err(FindError::Ambiguous { found, shadowed }) => {
+                assert_eq!(found, modern);
+                assert_eq!(shadowed, vec![legacy.clone()]);
+            }
+            other => panic!("expected ambiguity, got {:?}", other),
+        }
+
+        fs::remove_file(&legacy).unwrap();
+        let report = find_faf_file_detailed(Some(&child), &DiscoveryOptions::default());
+        assert!(!report.is_ambiguous());
+        assert_eq!(report.into_result().unwrap().path, modern);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_detailed_report_unreadable() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".faf"), "faf_version: 2.5.0").unwrap();
+        // A symlink loop fails metadata() with something other than NotFound
+        let looped = dir.path().join("project.faf");
+        std::os::unix::fs::symlink(&looped, &looped).unwrap();
+
+        // find_faf_file quietly falls back to the legacy file
+        assert_eq!(find_faf_file(Some(dir.path())), Some(dir.path().join(".faf")));
+
+        let report = find_faf_file_detailed(Some(dir.path()), &DiscoveryOptions::default());
+        assert_eq!(report.unreadable.len(), 1);
+        assert_eq!(report.unreadable[0].path, looped);
+        assert!(matches!(report.into_result(), Err(FindError::Unreadable(path, _)) if path == looped));
     }
 }
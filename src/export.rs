@@ -0,0 +1,247 @@
+//! Export FAF context to agent instruction files
+//!
+//! Generated text sits between [`EXPORT_BEGIN`] and [`EXPORT_END`] marker
+//! comments. Regenerating replaces only that region, so hand-written text
+//! elsewhere in the file is kept.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::parser::{FafError, FafFile};
+use crate::render::{render, Format, RenderOptions};
+
+/// First line of the generated region
+pub const EXPORT_BEGIN: &str =
+    "<!-- faf:begin - generated from project.faf, edits inside this block are overwritten -->";
+
+/// Last line of the generated region
+pub const EXPORT_END: &str = "<!-- faf:end -->";
+
+/// Agent instruction file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportTarget {
+    /// `CLAUDE.md`
+    ClaudeMd,
+    /// `AGENTS.md`
+    AgentsMd,
+    /// `.cursorrules`
+    CursorRules,
+    /// `.github/copilot-instructions.md`
+    CopilotInstructions,
+}
+
+impl ExportTarget {
+    /// All targets
+    pub const ALL: [ExportTarget; 4] = [
+        ExportTarget::ClaudeMd,
+        ExportTarget::AgentsMd,
+        ExportTarget::CursorRules,
+        ExportTarget::CopilotInstructions,
+    ];
+
+    /// Short name (`claude-md`, `agents-md`, `cursorrules`, `copilot`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportTarget::ClaudeMd => "claude-md",
+            ExportTarget::AgentsMd => "agents-md",
+            ExportTarget::CursorRules => "cursorrules",
+            ExportTarget::CopilotInstructions => "copilot",
+        }
+    }
+
+    /// Look up a target by short name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    /// File path relative to the project root
+    pub fn path(&self) -> &'static str {
+        match self {
+            ExportTarget::ClaudeMd => "CLAUDE.md",
+            ExportTarget::AgentsMd => "AGENTS.md",
+            ExportTarget::CursorRules => ".cursorrules",
+            ExportTarget::CopilotInstructions => ".github/copilot-instructions.md",
+        }
+    }
+
+    fn format(&self) -> Format {
+        match self {
+            ExportTarget::CursorRules => Format::PlainText,
+            _ => Format::Markdown,
+        }
+    }
+}
+
+/// Generated region for a target, markers included
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{export, parse, ExportTarget, EXPORT_BEGIN};
+///
+/// let faf = parse("faf_version: 2.5.0\nproject:\n  name: demo\n  goal: Testing").unwrap();
+/// let text = export(&faf, ExportTarget::ClaudeMd);
+/// assert!(text.starts_with(EXPORT_BEGIN));
+/// assert!(text.contains("- **Goal:** Testing"));
+/// ```
+pub fn export(faf: &FafFile, target: ExportTarget) -> String {
+    let body = render(&faf.data, target.format(), &RenderOptions::default());
+    let title = match target.format() {
+        Format::PlainText => format!("{}\n", faf.project_name()),
+        _ => format!("# {}\n", faf.project_name()),
+    };
+    format!("{}\n{}\n{}{}\n", EXPORT_BEGIN, title, body, EXPORT_END)
+}
+
+/// Replace the generated region in existing file content
+///
+/// Text outside the markers is kept. Content without markers gets the
+/// region prepended; a begin marker without an end marker (or the reverse)
+/// is an error rather than a guess.
+pub fn export_merge(
+    faf: &FafFile,
+    target: ExportTarget,
+    existing: &str,
+) -> Result<String, FafError> {
+    let generated = export(faf, target);
+    let lines: Vec<&str> = existing.split_inclusive('\n').collect();
+    let begin = lines.iter().position(|l| l.trim_end() == EXPORT_BEGIN);
+    let end = lines.iter().position(|l| l.trim_end() == EXPORT_END);
+
+    match (begin, end) {
+        (None, None) if existing.trim().is_empty() => Ok(generated),
+        (None, None) => Ok(format!("{}\n{}", generated, existing)),
+        (Some(begin), Some(end)) if begin < end => Ok(format!(
+            "{}{}{}",
+            lines[..begin].concat(),
+            generated,
+            lines[end + 1..].concat()
+        )),
+        _ => Err(FafError::InvalidMarkers(target.path().to_string())),
+    }
+}
+
+/// Write or update a target file under `root`, returning its path
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{export_to, parse_file, ExportTarget};
+///
+/// let faf = parse_file("project.faf").unwrap();
+/// for target in ExportTarget::ALL {
+///     export_to(&faf, target, ".").unwrap();
+/// }
+/// ```
+pub fn export_to<P: AsRef<Path>>(
+    faf: &FafFile,
+    target: ExportTarget,
+    root: P,
+) -> Result<PathBuf, FafError> {
+    let path = root.as_ref().join(target.path());
+    let existing = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let content = export_merge(faf, target, &existing)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, content)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn sample(goal: &str) -> FafFile {
+        parse(&format!(
+            "faf_version: 2.5.0\nproject:\n  name: demo\n  goal: {}\ntags: [rust]",
+            goal
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_export_targets() {
+        let faf = sample("Testing");
+        assert_eq!(
+            export(&faf, ExportTarget::AgentsMd),
+            format!(
+                "{}\n# demo\n\n## Project\n\n- **Name:** demo\n- **Goal:** Testing\n\n## Tags\n\n- rust\n{}\n",
+                EXPORT_BEGIN, EXPORT_END
+            )
+        );
+        assert!(export(&faf, ExportTarget::CursorRules).contains("\ndemo\n\nPROJECT\nName: demo\n"));
+        assert_eq!(
+            ExportTarget::from_name("copilot"),
+            Some(ExportTarget::CopilotInstructions)
+        );
+        assert_eq!(ExportTarget::from_name("vim"), None);
+    }
+
+    #[test]
+    fn test_merge_keeps_hand_written_text() {
+        let header = "# Notes\n\nKeep this.\n\n";
+        let footer = "\n## Team rules\n\nNo force pushes.\n";
+
+        let first = export_merge(&sample("Old"), ExportTarget::ClaudeMd, "Keep this.\n").unwrap();
+        assert!(first.starts_with(EXPORT_BEGIN));
+        assert!(first.ends_with("\nKeep this.\n"));
+
+        let existing = format!(
+            "{}{}{}",
+            header,
+            export(&sample("Old"), ExportTarget::ClaudeMd),
+            footer
+        );
+        let updated = export_merge(&sample("New"), ExportTarget::ClaudeMd, &existing).unwrap();
+        assert!(updated.starts_with(header));
+        assert!(updated.ends_with(footer));
+        assert!(updated.contains("**Goal:** New"));
+        assert!(!updated.contains("**Goal:** Old"));
+
+        // Regenerating is stable
+        assert_eq!(
+            export_merge(&sample("New"), ExportTarget::ClaudeMd, &updated).unwrap(),
+            updated
+        );
+
+        let broken = format!("{}\nhalf a block\n", EXPORT_BEGIN);
+        assert!(matches!(
+            export_merge(&sample("New"), ExportTarget::ClaudeMd, &broken),
+            Err(FafError::InvalidMarkers(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_to_creates_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = export_to(
+            &sample("Testing"),
+            ExportTarget::CopilotInstructions,
+            dir.path(),
+        )
+        .unwrap();
+        assert_eq!(path, dir.path().join(".github/copilot-instructions.md"));
+
+        fs::write(
+            &path,
+            format!("{}Extra\n", fs::read_to_string(&path).unwrap()),
+        )
+        .unwrap();
+        export_to(
+            &sample("Changed"),
+            ExportTarget::CopilotInstructions,
+            dir.path(),
+        )
+        .unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("**Goal:** Changed"));
+        assert!(content.ends_with(&format!("{}\nExtra\n", EXPORT_END)));
+    }
+}
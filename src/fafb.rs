@@ -0,0 +1,397 @@
+//! Compile FAF to the .fafb binary format (see FAFB-BINARY-SPEC.md)
+//!
+//! Implements the core layer: 32-byte header, META, TECH_STACK, KEY_FILES,
+//! COMMANDS and CONTEXT sections, and the trailing section table. All
+//! integers are little-endian.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::parser::{FafError, FafFile};
+use crate::scoring::ScoringModel;
+use crate::types::FafData;
+
+/// File magic, bytes 0-3
+pub const FAFB_MAGIC: &[u8; 4] = b"FAFB";
+/// Format version written by this SDK
+pub const FAFB_VERSION: (u8, u8) = (1, 0);
+
+const HEADER_SIZE: usize = 32;
+const ENTRY_SIZE: usize = 16;
+const MAX_SECTIONS: usize = 256;
+
+pub const SECTION_META: u8 = 0x01;
+pub const SECTION_TECH_STACK: u8 = 0x02;
+pub const SECTION_KEY_FILES: u8 = 0x03;
+pub const SECTION_COMMANDS: u8 = 0x05;
+pub const SECTION_CONTEXT: u8 = 0x06;
+
+/// Decoded .fafb header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FafbHeader {
+    pub version_major: u8,
+    pub version_minor: u8,
+    pub flags: u16,
+    /// CRC32 of the source YAML
+    pub source_checksum: u32,
+    /// Unix timestamp of compilation
+    pub created_timestamp: u64,
+    pub section_count: u16,
+    pub section_table_offset: u32,
+    pub total_size: u32,
+}
+
+/// One section from the section table, with its data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FafbSection {
+    pub section_type: u8,
+    /// Truncation priority, higher is more important
+    pub priority: u8,
+    pub token_count: u16,
+    pub flags: u16,
+    pub data: Vec<u8>,
+}
+
+/// A loaded .fafb file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FafBinary {
+    pub header: FafbHeader,
+    pub sections: Vec<FafbSection>,
+}
+
+impl FafBinary {
+    /// Parse and bounds-check a .fafb file
+    ///
+    /// Rejects a bad magic number or major version; unknown section types
+    /// and flags are kept as-is.
+    pub fn load(bytes: &[u8]) -> Result<Self, FafError> {
+        let invalid = |msg: &str| FafError::InvalidFafb(msg.to_string());
+        if bytes.len() < HEADER_SIZE || &bytes[0..4] != FAFB_MAGIC {
+            return Err(invalid("not a .fafb file"));
+        }
+
+        let u16_at = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+        let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        let header = FafbHeader {
+            version_major: bytes[4],
+            version_minor: bytes[5],
+            flags: u16_at(6),
+            source_checksum: u32_at(8),
+            created_timestamp: u64::from_le_bytes(bytes[12..20].try_into().unwrap()),
+            section_count: u16_at(20),
+            section_table_offset: u32_at(22),
+            total_size: u32_at(28),
+        };
+
+        if header.version_major != FAFB_VERSION.0 {
+            return Err(invalid("unsupported major version"));
+        }
+        if header.total_size as usize != bytes.len() {
+            return Err(invalid("size does not match header"));
+        }
+        let count = header.section_count as usize;
+        let table = header.section_table_offset as usize;
+        // Optional blocks (embeddings, token map) may follow the table
+        let table_end = table.checked_add(count * ENTRY_SIZE);
+        if count > MAX_SECTIONS || table_end.is_none_or(|end| end > bytes.len()) {
+            return Err(invalid("section table out of bounds"));
+        }
+
+        let sections = (0..count)
+            .map(|i| {
+                let at = table + i * ENTRY_SIZE;
+                let offset = u32_at(at + 2) as usize;
+                let length = u32_at(at + 6) as usize;
+                let data = offset
+                    .checked_add(length)
+                    .filter(|&end| offset >= HEADER_SIZE && end <= table)
+                    .map(|end| bytes[offset..end].to_vec())
+                    .ok_or_else(|| invalid("section data out of bounds"))?;
+                Ok(FafbSection {
+                    section_type: bytes[at],
+                    priority: bytes[at + 1],
+                    token_count: u16_at(at + 10),
+                    flags: u16_at(at + 12),
+                    data,
+                })
+            })
+            .collect::<Result<Vec<_>, FafError>>()?;
+
+        Ok(FafBinary { header, sections })
+    }
+
+    /// First section of a type
+    pub fn section(&self, section_type: u8) -> Option<&FafbSection> {
+        self.sections
+            .iter()
+            .find(|s| s.section_type == section_type)
+    }
+}
+
+/// Compile a parsed file to .fafb bytes
+///
+/// `source` is the original YAML; its CRC32 goes in the header so readers
+/// can tell when the binary is stale.
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{compile_fafb, parse, FafBinary, SECTION_META};
+///
+/// let source = "faf_version: 2.5.0\nproject:\n  name: demo";
+/// let bytes = compile_fafb(&parse(source).unwrap(), source).unwrap();
+/// assert_eq!(&bytes[0..4], b"FAFB");
+///
+/// let binary = FafBinary::load(&bytes).unwrap();
+/// assert!(binary.section(SECTION_META).is_some());
+/// ```
+pub fn compile_fafb(faf: &FafFile, source: &str) -> Result<Vec<u8>, FafError> {
+    let data = &faf.data;
+    let mut sections = vec![
+        (SECTION_META, 255, meta(data)?),
+        (SECTION_TECH_STACK, 200, pairs(tech_stack(data))?),
+        (SECTION_KEY_FILES, 200, key_files(data)?),
+    ];
+    if let Some(ic) = data
+        .instant_context
+        .as_ref()
+        .filter(|ic| !ic.commands.is_empty())
+    {
+        let mut commands: Vec<_> = ic
+            .commands
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        commands.sort();
+        sections.push((SECTION_COMMANDS, 180, pairs(commands)?));
+    }
+    let context = context(data)?;
+    if !context.is_empty() {
+        sections.push((SECTION_CONTEXT, 64, context));
+    }
+
+    let mut out = vec![0u8; HEADER_SIZE];
+    let mut table = Vec::with_capacity(sections.len() * ENTRY_SIZE);
+    for (section_type, priority, bytes) in &sections {
+        let tokens = (bytes.len() / 4).min(u16::MAX as usize) as u16;
+        table.push(*section_type);
+        table.push(*priority);
+        table.extend_from_slice(&(out.len() as u32).to_le_bytes());
+        table.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        table.extend_from_slice(&tokens.to_le_bytes());
+        table.extend_from_slice(&0u16.to_le_bytes()); // flags
+        table.extend_from_slice(&[0, 0]); // reserved
+        out.extend_from_slice(bytes);
+    }
+    let table_offset = out.len();
+    out.extend_from_slice(&table);
+
+    let created = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    header.extend_from_slice(FAFB_MAGIC);
+    header.extend_from_slice(&[FAFB_VERSION.0, FAFB_VERSION.1]);
+    header.extend_from_slice(&0u16.to_le_bytes()); // flags
+    header.extend_from_slice(&crc32fast::hash(source.as_bytes()).to_le_bytes());
+    header.extend_from_slice(&created.to_le_bytes());
+    header.extend_from_slice(&(sections.len() as u16).to_le_bytes());
+    header.extend_from_slice(&(table_offset as u32).to_le_bytes());
+    header.extend_from_slice(&0u16.to_le_bytes()); // reserved
+    header.extend_from_slice(&(out.len() as u32).to_le_bytes());
+    out[..HEADER_SIZE].copy_from_slice(&header);
+    Ok(out)
+}
+
+fn too_long(what: &str) -> FafError {
+    FafError::InvalidFafb(format!("{} too long for .fafb", what))
+}
+
+/// `len: u8` then UTF-8 bytes
+fn put_short(out: &mut Vec<u8>, text: &str, what: &str) -> Result<(), FafError> {
+    let len = u8::try_from(text.len()).map_err(|_| too_long(what))?;
+    out.push(len);
+    out.extend_from_slice(text.as_bytes());
+    Ok(())
+}
+
+/// `len: u16` then UTF-8 bytes
+fn put_long(out: &mut Vec<u8>, text: &str, what: &str) -> Result<(), FafError> {
+    let len = u16::try_from(text.len()).map_err(|_| too_long(what))?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(text.as_bytes());
+    Ok(())
+}
+
+fn meta(data: &FafData) -> Result<Vec<u8>, FafError> {
+    let mut out = Vec::new();
+    put_long(&mut out, &data.project.name, "project.name")?;
+    put_short(&mut out, &data.faf_version, "faf_version")?;
+    out.push(ScoringModel::default().score(data).score);
+    Ok(out)
+}
+
+fn tech_stack(data: &FafData) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    if let Some(tech) = data
+        .instant_context
+        .as_ref()
+        .and_then(|ic| ic.tech_stack.clone())
+    {
+        entries.push(("tech_stack".to_string(), tech));
+    }
+    if let Some(stack) = &data.stack {
+        let fields = [
+            ("frontend", &stack.frontend),
+            ("backend", &stack.backend),
+            ("database", &stack.database),
+            ("infrastructure", &stack.infrastructure),
+            ("build_tool", &stack.build_tool),
+            ("testing", &stack.testing),
+            ("cicd", &stack.cicd),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                entries.push((key.to_string(), value.clone()));
+            }
+        }
+    }
+    entries
+}
+
+/// `entry_count: u16` then `key: u8-prefixed`, `value: u16-prefixed`
+fn pairs(entries: Vec<(String, String)>) -> Result<Vec<u8>, FafError> {
+    let count = u16::try_from(entries.len()).map_err(|_| too_long("section"))?;
+    let mut out = count.to_le_bytes().to_vec();
+    for (key, value) in entries {
+        put_short(&mut out, &key, "key")?;
+        put_long(&mut out, &value, "value")?;
+    }
+    Ok(out)
+}
+
+fn key_files(data: &FafData) -> Result<Vec<u8>, FafError> {
+    let files = data
+        .instant_context
+        .as_ref()
+        .map(|ic| ic.key_files.as_slice())
+        .unwrap_or_default();
+    let count = u16::try_from(files.len()).map_err(|_| too_long("key_files"))?;
+    let mut out = count.to_le_bytes().to_vec();
+    for file in files {
+        put_long(&mut out, file, "key file")?;
+        put_long(&mut out, "", "description")?;
+    }
+    Ok(out)
+}
+
+/// Remaining human-facing sections as UTF-8 YAML
+fn context(data: &FafData) -> Result<Vec<u8>, FafError> {
+    let rest = FafData {
+        faf_version: String::new(),
+        project: Default::default(),
+        ai_tldr: data.ai_tldr.clone(),
+        human_context: data.human_context.clone(),
+        preferences: data.preferences.clone(),
+        state: data.state.clone(),
+        tags: data.tags.clone(),
+        ..Default::default()
+    };
+    let mut value = serde_yaml::to_value(&rest)?;
+    if let Some(map) = value.as_mapping_mut() {
+        map.remove("faf_version");
+        map.remove("project");
+        if map.is_empty() {
+            return Ok(Vec::new());
+        }
+    }
+    Ok(serde_yaml::to_string(&value)?.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const SOURCE: &str = include_str!("../examples/full.faf");
+
+    #[test]
+    fn test_compile_layout() {
+        let bytes = compile_fafb(&parse(SOURCE).unwrap(), SOURCE).unwrap();
+        let binary = FafBinary::load(&bytes).unwrap();
+        let header = &binary.header;
+
+        assert_eq!((header.version_major, header.version_minor), (1, 0));
+        assert_eq!(header.source_checksum, crc32fast::hash(SOURCE.as_bytes()));
+        assert_eq!(header.total_size as usize, bytes.len());
+        let types: Vec<u8> = binary.sections.iter().map(|s| s.section_type).collect();
+        assert_eq!(
+            types,
+            [
+                SECTION_META,
+                SECTION_TECH_STACK,
+                SECTION_KEY_FILES,
+                SECTION_COMMANDS,
+                SECTION_CONTEXT
+            ]
+        );
+
+        let meta = &binary.section(SECTION_META).unwrap().data;
+        assert_eq!(&meta[0..2], &12u16.to_le_bytes());
+        assert_eq!(&meta[2..14], b"full-example");
+        assert_eq!(meta[14] as usize, "2.5.0".len());
+        assert_eq!(*meta.last().unwrap(), 96);
+
+        let key_files = &binary.section(SECTION_KEY_FILES).unwrap();
+        assert_eq!(&key_files.data[0..2], &3u16.to_le_bytes());
+        assert_eq!(key_files.priority, 200);
+        let context = &binary.section(SECTION_CONTEXT).unwrap().data;
+        assert!(std::str::from_utf8(context)
+            .unwrap()
+            .contains("why: Demonstrate all FAF capabilities"));
+    }
+
+    #[test]
+    fn test_load_rejects_corruption() {
+        let bytes = compile_fafb(&parse(SOURCE).unwrap(), SOURCE).unwrap();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = 0;
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 9;
+        let mut bad_offset = bytes.clone();
+        let table = u32::from_le_bytes(bytes[22..26].try_into().unwrap()) as usize;
+        bad_offset[table + 2..table + 6].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        for corrupt in [
+            &bad_magic,
+            &bad_version,
+            &bad_offset,
+            &bytes[..bytes.len() - 1].to_vec(),
+        ] {
+            assert!(matches!(
+                FafBinary::load(corrupt),
+                Err(FafError::InvalidFafb(_))
+            ));
+        }
+        assert!(FafBinary::load(b"FAFB").is_err());
+
+        let mut bad_table = bytes.clone();
+        let past_end = (bytes.len() - ENTRY_SIZE + 1) as u32;
+        bad_table[22..26].copy_from_slice(&past_end.to_le_bytes());
+        assert!(FafBinary::load(&bad_table).is_err());
+    }
+
+    #[test]
+    fn test_load_allows_trailing_blocks() {
+        let mut bytes = compile_fafb(&parse(SOURCE).unwrap(), SOURCE).unwrap();
+        let plain = FafBinary::load(&bytes).unwrap();
+
+        // An embeddings block after the section table, as the spec allows
+        bytes.extend_from_slice(&[0xEE; 32]);
+        let total = bytes.len() as u32;
+        bytes[28..32].copy_from_slice(&total.to_le_bytes());
+        let loaded = FafBinary::load(&bytes).unwrap();
+        assert_eq!(loaded.sections, plain.sections);
+        assert_eq!(loaded.header.total_size, total);
+    }
+}
@@ -0,0 +1,198 @@
+//! Content fingerprints: a hash of what a FAF file says, not how it is written
+//!
+//! The data is hashed in a canonical form: map keys sorted (so the order of
+//! `commands` and `ai_tldr` entries does not matter), and absent, null and
+//! empty values treated alike. Quoting, comments and layout never reach the
+//! parsed data, so they do not count either.
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::types::FafData;
+
+/// Fields tools rewrite on their own (see [`update_declared_scores`](crate::update_declared_scores))
+pub const VOLATILE_FIELDS: &[&str] = &["ai_score", "ai_confidence", "context_quality.slots_filled"];
+
+/// What [`FafData::fingerprint_with`] leaves out
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FingerprintOptions {
+    /// Dotted paths to ignore (`ai_score`, `instant_context.commands.dev`)
+    pub exclude: Vec<String>,
+}
+
+impl FingerprintOptions {
+    /// Ignore [`VOLATILE_FIELDS`]
+    pub fn without_volatile() -> Self {
+        FingerprintOptions {
+            exclude: VOLATILE_FIELDS.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+}
+
+impl FafData {
+    /// SHA-256 of the canonical content
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use faf_rust_sdk::parse;
+    ///
+    /// let a = parse("faf_version: 2.5.0\nproject:\n  name: demo\n  goal: Ship it\n").unwrap();
+    /// let b = parse("project: {goal: 'Ship it', name: demo}\nfaf_version: '2.5.0'").unwrap();
+    /// assert_eq!(a.data.fingerprint(), b.data.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> [u8; 32] {
+        self.fingerprint_with(&FingerprintOptions::default())
+    }
+
+    /// SHA-256 of the canonical content, leaving out `options.exclude`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use faf_rust_sdk::{parse, FingerprintOptions};
+    ///
+    /// let a = parse("faf_version: 2.5.0\nai_score: 40%\nproject:\n  name: demo").unwrap();
+    /// let b = parse("faf_version: 2.5.0\nai_score: 55%\nproject:\n  name: demo").unwrap();
+    /// let options = FingerprintOptions::without_volatile();
+    /// assert_ne!(a.data.fingerprint(), b.data.fingerprint());
+    /// assert_eq!(a.data.fingerprint_with(&options), b.data.fingerprint_with(&options));
+    /// ```
+    pub fn fingerprint_with(&self, options: &FingerprintOptions) -> [u8; 32] {
+        let mut root = serde_json::to_value(self).unwrap_or(Value::Null);
+        for path in &options.exclude {
+            remove(&mut root, &path.split('.').collect::<Vec<_>>());
+        }
+        let mut out = String::new();
+        canonical(&root, &mut out);
+        Sha256::digest(out.as_bytes()).into()
+    }
+}
+
+fn remove(value: &mut Value, path: &[&str]) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut node = value;
+    for segment in parents {
+        match node.get_mut(*segment) {
+            Some(child) => node = child,
+            None => return,
+        }
+    }
+    if let Value::Object(map) = node {
+        map.remove(*last);
+    }
+}
+
+/// Whether a value counts as absent
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.values().all(is_empty),
+        _ => false,
+    }
+}
+
+/// Compact JSON with sorted keys and empty values dropped
+fn canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().filter(|(_, v)| !is_empty(v)).collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const FULL: &str = include_str!("../examples/full.faf");
+
+    #[test]
+    fn test_fingerprint_ignores_layout_and_order() {
+        let a = parse(FULL).unwrap();
+        // Same data as JSON: different layout and quoting, no comments
+        let b = parse(&serde_json::to_string(&a.data).unwrap()).unwrap();
+        assert_eq!(a.data.fingerprint(), b.data.fingerprint());
+
+        let forward = "faf_version: 2.5.0\nproject:\n  name: demo\ninstant_context:\n  commands:\n    build: make\n    test: make test\n";
+        let backward = "instant_context:\n  commands: {test: make test, build: make}\nproject: {name: demo}\nfaf_version: \"2.5.0\"\n";
+        assert_eq!(
+            parse(forward).unwrap().data.fingerprint(),
+            parse(backward).unwrap().data.fingerprint()
+        );
+
+        // Every parse shuffles the HashMaps differently
+        for _ in 0..10 {
+            assert_eq!(
+                parse(FULL).unwrap().data.fingerprint(),
+                a.data.fingerprint()
+            );
+        }
+
+        // Missing and empty sections are the same content
+        let bare = parse("faf_version: 2.5.0\nproject:\n  name: demo").unwrap();
+        let empty =
+            parse("faf_version: 2.5.0\nproject:\n  name: demo\nstack: {}\ntags: []").unwrap();
+        assert_eq!(bare.data.fingerprint(), empty.data.fingerprint());
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_content() {
+        let base = parse(FULL).unwrap().data;
+        let mut changed = base.clone();
+        changed
+            .instant_context
+            .as_mut()
+            .unwrap()
+            .commands
+            .insert("lint".to_string(), "cargo clippy".to_string());
+        assert_ne!(base.fingerprint(), changed.fingerprint());
+
+        let mut rescored = base.clone();
+        rescored.ai_score = Some("99%".to_string());
+        rescored
+            .context_quality
+            .get_or_insert_with(Default::default)
+            .slots_filled = Some("1/1".to_string());
+        assert_ne!(base.fingerprint(), rescored.fingerprint());
+        let options = FingerprintOptions::without_volatile();
+        assert_eq!(
+            base.fingerprint_with(&options),
+            rescored.fingerprint_with(&options)
+        );
+
+        let options = FingerprintOptions {
+            exclude: vec!["instant_context.commands.lint".to_string()],
+        };
+        assert_eq!(
+            base.fingerprint_with(&options),
+            changed.fingerprint_with(&options)
+        );
+    }
+}
@@ -0,0 +1,416 @@
+//! Auto-fix - apply safe validator suggestions
+
+use crate::parser::FafFile;
+use crate::rules::{filled_missing_context, normalize_score, HANDOFF_MIN_SCORE};
+use crate::scoring::{slots_filled, ScoringModel};
+use crate::types::{FafData, HumanContext, InstantContext, Stack};
+
+/// Which fixes to apply
+#[derive(Debug, Clone)]
+pub struct FixOptions {
+    /// Rewrite `ai_score` as `NN%` ("85 %", "85", "85.4%" become "85%")
+    pub normalize_score: bool,
+    /// Remove repeated entries from tags, key_files, milestones and missing_context
+    pub dedupe_lists: bool,
+    /// Sort key_files alphabetically. Off by default: order is priority,
+    /// and compression keeps only the first few entries
+    pub sort_key_files: bool,
+    /// Add empty instant_context, stack and human_context sections
+    pub add_missing_sections: bool,
+    /// Recompute `context_quality.slots_filled` (only if the section exists)
+    pub update_slots_filled: bool,
+    /// Report fixes without changing anything
+    pub dry_run: bool,
+}
+
+impl Default for FixOptions {
+    fn default() -> Self {
+        FixOptions {
+            normalize_score: true,
+            dedupe_lists: true,
+            sort_key_files: false,
+            add_missing_sections: true,
+            update_slots_filled: true,
+            dry_run: false,
+        }
+    }
+}
+
+/// A fix that was applied (or would be, in dry-run mode)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedFix {
+    /// Validator rule the fix addresses, if any (`FAF008`)
+    pub rule: Option<String>,
+    /// Dotted path of the changed field
+    pub path: String,
+    /// What changed
+    pub description: String,
+}
+
+impl AppliedFix {
+    fn new(rule: Option<&str>, path: &str, description: String) -> Self {
+        AppliedFix {
+            rule: rule.map(str::to_string),
+            path: path.to_string(),
+            description,
+        }
+    }
+}
+
+/// Apply safe fixes and report what changed
+///
+/// Returns the fixed file and the list of fixes. In dry-run mode the
+/// returned file is an unchanged copy and the list says what would change.
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{fix, parse, FixOptions};
+///
+/// let faf = parse(r#"
+/// faf_version: 2.5.0
+/// ai_score: "85 %"
+/// project:
+///   name: test
+/// tags: [rust, rust]
+/// "#).unwrap();
+///
+/// let (fixed, applied) = fix(&faf, &FixOptions::default());
+/// assert_eq!(fixed.data.ai_score.as_deref(), Some("85%"));
+/// assert_eq!(fixed.data.tags, vec!["rust"]);
+/// assert!(!applied.is_empty());
+/// ```
+pub fn fix(faf: &FafFile, options: &FixOptions) -> (FafFile, Vec<AppliedFix>) {
+    let mut fixed = faf.clone();
+    let mut applied = Vec::new();
+    let data = &mut fixed.data;
+
+    if options.normalize_score {
+        if let Some(raw) = &data.ai_score {
+            if let Some(normalized) = normalize_score(raw).filter(|n| n != raw) {
+                applied.push(AppliedFix::new(
+                    Some("FAF008"),
+                    "ai_score",
+                    format!("normalized '{}' to '{}'", raw, normalized),
+                ));
+                data.ai_score = Some(normalized);
+            }
+        }
+    }
+
+    if options.dedupe_lists {
+        dedupe(&mut data.tags, Some("FAF009"), "tags", &mut applied);
+        if let Some(ic) = &mut data.instant_context {
+            dedupe(
+                &mut ic.key_files,
+                Some("FAF010"),
+                "instant_context.key_files",
+                &mut applied,
+            );
+        }
+        if let Some(state) = &mut data.state {
+            dedupe(
+                &mut state.milestones,
+                None,
+                "state.milestones",
+                &mut applied,
+            );
+        }
+        if let Some(cq) = &mut data.context_quality {
+            dedupe(
+                &mut cq.missing_context,
+                None,
+                "context_quality.missing_context",
+                &mut applied,
+            );
+        }
+    }
+
+    if options.sort_key_files {
+        if let Some(ic) = &mut data.instant_context {
+            if !ic.key_files.is_sorted() {
+                ic.key_files.sort();
+                applied.push(AppliedFix::new(
+                    None,
+                    "instant_context.key_files",
+                    "sorted entries".to_string(),
+                ));
+            }
+        }
+    }
+
+    if options.add_missing_sections {
+        if data.instant_context.is_none() {
+            data.instant_context = Some(InstantContext::default());
+            applied.push(added_section("FAF003", "instant_context"));
+        }
+        if data.stack.is_none() {
+            data.stack = Some(Stack::default());
+            applied.push(added_section("FAF006", "stack"));
+        }
+        if data.human_context.is_none() {
+            data.human_context = Some(HumanContext::default());
+            applied.push(added_section("FAF007", "human_context"));
+        }
+    }
+
+    if options.update_slots_filled {
+        let (filled, total) = slots_filled(data);
+        let slots = format!("{}/{}", filled, total);
+        if let Some(cq) = &mut data.context_quality {
+            if cq.slots_filled.as_deref() != Some(slots.as_str()) {
+                applied.push(AppliedFix::new(
+                    Some("FAF012"),
+                    "context_quality.slots_filled",
+                    format!(
+                        "updated '{}' to '{}'",
+                        cq.slots_filled.as_deref().unwrap_or(""),
+                        slots
+                    ),
+                ));
+                cq.slots_filled = Some(slots);
+            }
+        }
+    }
+
+    if options.dry_run {
+        (faf.clone(), applied)
+    } else {
+        (fixed, applied)
+    }
+}
+
+/// Rewrite self-reported scores from the computed ones
+///
+/// Sets `ai_score` to the default-model score and, when a
+/// `context_quality` section exists, recomputes `slots_filled`, clears
+/// `handoff_ready` below 70% and drops `missing_context` entries for
+/// fields that are filled. `handoff_ready` is never switched on.
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{parse, update_declared_scores};
+///
+/// let mut faf = parse("faf_version: 2.5.0\nai_score: \"99%\"\nproject:\n  name: test").unwrap();
+/// let applied = update_declared_scores(&mut faf.data);
+/// assert_eq!(faf.data.ai_score.as_deref(), Some("4%"));
+/// assert_eq!(applied[0].path, "ai_score");
+/// ```
+pub fn update_declared_scores(data: &mut FafData) -> Vec<AppliedFix> {
+    let mut applied = Vec::new();
+    let score = ScoringModel::default().score(data).score;
+    let (filled, total) = slots_filled(data);
+    let stale_missing = filled_missing_context(data);
+
+    let declared = format!("{}%", score);
+    if data.ai_score.as_deref() != Some(declared.as_str()) {
+        applied.push(AppliedFix::new(
+            Some("FAF011"),
+            "ai_score",
+            format!(
+                "updated '{}' to '{}'",
+                data.ai_score.as_deref().unwrap_or(""),
+                declared
+            ),
+        ));
+        data.ai_score = Some(declared);
+    }
+
+    let Some(cq) = &mut data.context_quality else {
+        return applied;
+    };
+
+    let slots = format!("{}/{}", filled, total);
+    if cq.slots_filled.as_deref() != Some(slots.as_str()) {
+        applied.push(AppliedFix::new(
+            Some("FAF012"),
+            "context_quality.slots_filled",
+            format!(
+                "updated '{}' to '{}'",
+                cq.slots_filled.as_deref().unwrap_or(""),
+                slots
+            ),
+        ));
+        cq.slots_filled = Some(slots);
+    }
+
+    if cq.handoff_ready && score < HANDOFF_MIN_SCORE {
+        cq.handoff_ready = false;
+        applied.push(AppliedFix::new(
+            Some("FAF013"),
+            "context_quality.handoff_ready",
+            format!("cleared: score {}% is below {}%", score, HANDOFF_MIN_SCORE),
+        ));
+    }
+
+    for (entry, path) in stale_missing {
+        cq.missing_context.retain(|e| *e != entry);
+        applied.push(AppliedFix::new(
+            Some("FAF014"),
+            "context_quality.missing_context",
+            format!("removed '{}' ({} is filled)", entry, path),
+        ));
+    }
+
+    applied
+}
+
+fn dedupe(list: &mut Vec<String>, rule: Option<&str>, path: &str, applied: &mut Vec<AppliedFix>) {
+    let before = list.len();
+    let mut seen = Vec::with_capacity(before);
+    list.retain(|item| {
+        if seen.contains(item) {
+            false
+        } else {
+            seen.push(item.clone());
+            true
+        }
+    });
+    if list.len() < before {
+        applied.push(AppliedFix::new(
+            rule,
+            path,
+            format!("removed {} duplicate entries", before - list.len()),
+        ));
+    }
+}
+
+fn added_section(rule: &str, path: &str) -> AppliedFix {
+    AppliedFix::new(Some(rule), path, "added empty section".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, validate, Severity};
+
+    const MESSY: &str = r#"
+faf_version: 2.5.0
+ai_score: "85.4 %"
+project:
+  name: test
+  goal: Testing
+instant_context:
+  what_building: App
+  tech_stack: Rust
+  key_files:
+    - src/main.rs
+    - src/lib.rs
+    - src/main.rs
+context_quality:
+  slots_filled: "20/24"
+tags:
+  - rust
+  - cli
+  - rust
+"#;
+
+    #[test]
+    fn test_fix_applies_safe_fixes() {
+        let faf = parse(MESSY).unwrap();
+        let (fixed, applied) = fix(&faf, &FixOptions::default());
+
+        assert_eq!(fixed.data.ai_score.as_deref(), Some("85%"));
+        assert_eq!(fixed.data.tags, vec!["rust", "cli"]);
+        assert_eq!(fixed.key_files(), ["src/main.rs", "src/lib.rs"]);
+        assert!(fixed.data.stack.is_some());
+        assert!(fixed.data.human_context.is_some());
+        assert_eq!(
+            fixed
+                .data
+                .context_quality
+                .as_ref()
+                .unwrap()
+                .slots_filled
+                .as_deref(),
+            Some("5/24")
+        );
+        assert!(applied.iter().any(|f| f.rule.as_deref() == Some("FAF008")));
+        assert!(!validate(&faf).warnings.is_empty());
+
+        // The fixes clear the matching warnings; only the declared score
+        // drift is left for update_declared_scores
+        let result = validate(&fixed);
+        let ids: Vec<_> = result.diagnostics_with(Severity::Warning).iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["FAF011"]);
+    }
+
+    #[test]
+    fn test_fix_dry_run() {
+        let faf = parse(MESSY).unwrap();
+        let options = FixOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let (unchanged, applied) = fix(&faf, &options);
+
+        assert_eq!(unchanged.data.ai_score.as_deref(), Some("85.4 %"));
+        assert_eq!(unchanged.data.tags.len(), 3);
+        assert_eq!(applied.len(), 6);
+    }
+
+    #[test]
+    fn test_fix_is_idempotent() {
+        let faf = parse(MESSY).unwrap();
+        let options = FixOptions {
+            sort_key_files: true,
+            ..Default::default()
+        };
+        let (fixed, _) = fix(&faf, &options);
+        assert_eq!(fixed.key_files(), ["src/lib.rs", "src/main.rs"]);
+
+        let (_, applied) = fix(&fixed, &options);
+        assert!(applied.is_empty(), "{:?}", applied);
+    }
+
+    #[test]
+    fn test_fix_leaves_unparseable_score() {
+        let faf = parse("faf_version: 2.5.0\nai_score: CORRUPTED\nproject:\n  name: t").unwrap();
+        let (fixed, _) = fix(&faf, &FixOptions::default());
+        assert_eq!(fixed.data.ai_score.as_deref(), Some("CORRUPTED"));
+    }
+
+    #[test]
+    fn test_update_declared_scores() {
+        let mut faf = parse(
+            r#"
+faf_version: 2.5.0
+ai_score: "92%"
+project:
+  name: test
+  goal: Testing
+stack:
+  database: PostgreSQL
+context_quality:
+  slots_filled: "18/24"
+  handoff_ready: true
+  missing_context:
+    - database
+    - API documentation
+"#,
+        )
+        .unwrap();
+
+        let before = validate(&faf);
+        let ids: Vec<_> = before.diagnostics_with(Severity::Warning).iter().map(|d| d.id.clone()).collect();
+        for id in ["FAF011", "FAF012", "FAF013", "FAF014"] {
+            assert!(ids.iter().any(|i| i == id), "expected {} in {:?}", id, ids);
+        }
+
+        let applied = update_declared_scores(&mut faf.data);
+        assert_eq!(applied.len(), 4);
+        assert_eq!(faf.data.ai_score.as_deref(), Some("13%"));
+        let cq = faf.data.context_quality.as_ref().unwrap();
+        assert_eq!(cq.slots_filled.as_deref(), Some("3/24"));
+        assert!(!cq.handoff_ready);
+        assert_eq!(cq.missing_context, vec!["API documentation"]);
+
+        let after = validate(&faf);
+        assert!(!after
+            .diagnostics
+            .iter()
+            .any(|d| ["FAF011", "FAF012", "FAF013", "FAF014"].contains(&d.id.as_str())));
+        assert!(update_declared_scores(&mut faf.data).is_empty());
+    }
+}
@@ -0,0 +1,623 @@
+//! Canonical layout for FAF files
+//!
+//! Works line by line so comments survive: top-level sections are put in
+//! `FafData` field order, indentation is normalised, percentages are
+//! double-quoted and versions are only quoted where YAML would read them as
+//! numbers. The result always parses to the same data as the input.
+
+use serde_yaml::Value;
+
+use crate::parser::FafError;
+
+/// Top-level keys in `FafData` field order; unknown keys follow in file order
+const CANONICAL_ORDER: &[&str] = &[
+    "faf_version",
+    "project",
+    "ai_score",
+    "ai_confidence",
+    "ai_tldr",
+    "instant_context",
+    "context_quality",
+    "stack",
+    "human_context",
+    "preferences",
+    "state",
+    "tags",
+];
+
+/// Keys whose values are normalised as versions
+const VERSION_KEYS: &[&str] = &["faf_version", "version"];
+
+/// Formatter settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Spaces per nesting level (default 2)
+    pub indent: usize,
+    /// Blank line between sections (default true)
+    pub blank_lines: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            indent: 2,
+            blank_lines: true,
+        }
+    }
+}
+
+/// Rewrite FAF content in canonical layout
+///
+/// Formatting is idempotent. YAML the layout rules do not model (such as
+/// an alias that would move above its anchor) fails with
+/// [`FafError::CannotFormat`] rather than being rewritten with a different
+/// meaning.
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{format, FormatOptions};
+///
+/// let messy = "project:\n    name: app   # the crate\nai_score: '85%'\nfaf_version: \"2.5.0\"\n";
+/// let formatted = format(messy, &FormatOptions::default()).unwrap();
+/// assert_eq!(
+///     formatted,
+///     "faf_version: 2.5.0\n\nproject:\n  name: app # the crate\n\nai_score: \"85%\"\n"
+/// );
+/// ```
+pub fn format(content: &str, options: &FormatOptions) -> Result<String, FafError> {
+    if content.trim().is_empty() {
+        return Err(FafError::EmptyContent);
+    }
+    let original = versions_by_value(serde_yaml::from_str(content)?);
+
+    let doc = split(content);
+    let mut blocks: Vec<&Block> = doc.blocks.iter().collect();
+    blocks.sort_by_key(|b| {
+        CANONICAL_ORDER
+            .iter()
+            .position(|k| *k == b.key)
+            .unwrap_or(CANONICAL_ORDER.len())
+    });
+
+    let mut out: Vec<String> = doc
+        .header
+        .iter()
+        .map(|l| l.trim_end().to_string())
+        .collect();
+    let mut prev_scalar = None;
+    for block in blocks {
+        let lines = layout(block, options);
+        let scalar = lines.len() == 1;
+        let separate = options.blank_lines && prev_scalar.is_some_and(|p| !(p && scalar));
+        if separate
+            || (options.blank_lines
+                && prev_scalar.is_none()
+                && out.iter().any(|l| !l.starts_with("---")))
+        {
+            out.push(String::new());
+        }
+        out.extend(block.leading.iter().map(|c| c.trim().to_string()));
+        out.extend(lines);
+        prev_scalar = Some(scalar);
+    }
+    if !doc.footer.is_empty() {
+        if options.blank_lines {
+            out.push(String::new());
+        }
+        out.extend(doc.footer.iter().map(|c| c.trim().to_string()));
+    }
+    let formatted = out.join("\n") + "\n";
+
+    let reparsed = serde_yaml::from_str(&formatted).map(versions_by_value);
+    if reparsed.is_ok_and(|value| value == original) {
+        Ok(formatted)
+    } else {
+        Err(FafError::CannotFormat(
+            "canonical layout would change the document's meaning".to_string(),
+        ))
+    }
+}
+
+/// Compare numeric versions by value, so quoting them does not count as a
+/// change of meaning
+///
+/// `version: 1.10` reads as the number 1.1, but is quoted as written
+/// (`"1.10"`); both sides become 1.1 here.
+fn versions_by_value(value: Value) -> Value {
+    match value {
+        Value::Mapping(map) => Value::Mapping(
+            map.into_iter()
+                .map(|(k, v)| match (k.as_str(), v) {
+                    (Some(key), v) if VERSION_KEYS.contains(&key) => (k, version_number(v)),
+                    (_, v) => (k, versions_by_value(v)),
+                })
+                .collect(),
+        ),
+        Value::Sequence(items) => {
+            Value::Sequence(items.into_iter().map(versions_by_value).collect())
+        }
+        other => other,
+    }
+}
+
+fn version_number(value: Value) -> Value {
+    let number = match &value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) if is_version(s) => s.parse().ok(),
+        _ => None,
+    };
+    number.map_or(value, |n: f64| Value::Number(n.into()))
+}
+
+/// True if `content` is already in canonical layout (`faf fmt --check`)
+///
+/// Content [`format`] cannot handle is an error, never "formatted".
+pub fn is_formatted(content: &str, options: &FormatOptions) -> Result<bool, FafError> {
+    Ok(format(content, options)? == content)
+}
+
+/// A top-level key with everything nested under it
+struct Block<'a> {
+    key: String,
+    /// Column-0 comments directly above the key
+    leading: Vec<&'a str>,
+    /// The key line, then nested lines, comments and blanks
+    lines: Vec<&'a str>,
+}
+
+struct Document<'a> {
+    /// Comments and `---` before the first section
+    header: Vec<&'a str>,
+    blocks: Vec<Block<'a>>,
+    /// Comments after the last section
+    footer: Vec<&'a str>,
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+/// Split into top-level blocks
+///
+/// Column-0 comments go with the next top-level key; indented comments
+/// stay in the block they appear in.
+fn split(content: &str) -> Document<'_> {
+    let mut doc = Document {
+        header: Vec::new(),
+        blocks: Vec::new(),
+        footer: Vec::new(),
+    };
+    let mut pending: Vec<&str> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim_end();
+        let top_level = indent_of(line) == 0
+            && !line.is_empty()
+            && !is_comment(line)
+            && !line.starts_with("---")
+            && !line.starts_with("...");
+        if line.is_empty() || (is_comment(line) && indent_of(line) == 0) {
+            pending.push(line);
+        } else if is_comment(line) && !doc.blocks.is_empty() {
+            let block = doc.blocks.last_mut().unwrap();
+            block.lines.append(&mut pending);
+            block.lines.push(line);
+        } else if top_level && split_key(line).is_some() {
+            let key = split_key(line).map(|(k, _)| unquote(k).unwrap_or(k).to_string());
+            if doc.blocks.is_empty() {
+                // Comments separated from the first key by a blank line are a file header
+                let cut = pending
+                    .iter()
+                    .rposition(|l| l.is_empty())
+                    .map_or(0, |i| i + 1);
+                doc.header
+                    .extend(pending.drain(..cut).filter(|l| !l.is_empty()));
+            }
+            pending.retain(|l| !l.is_empty());
+            doc.blocks.push(Block {
+                key: key.unwrap_or_default(),
+                leading: std::mem::take(&mut pending),
+                lines: vec![line],
+            });
+        } else if let Some(block) = doc.blocks.last_mut() {
+            block.lines.append(&mut pending);
+            block.lines.push(line);
+        } else {
+            pending.retain(|l| !l.is_empty());
+            doc.header.append(&mut pending);
+            doc.header.push(line);
+        }
+    }
+    doc.footer = pending.into_iter().filter(|l| !l.is_empty()).collect();
+    doc
+}
+
+/// Open nesting level: original indent, new indent for children
+struct Level {
+    orig: usize,
+    new: usize,
+    child: usize,
+    item: bool,
+}
+
+/// Re-indent and normalise one block
+fn layout(block: &Block, options: &FormatOptions) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut stack: Vec<Level> = Vec::new();
+    let mut comments: Vec<&str> = Vec::new();
+    // Indent of a `key:` with no inline value, whose list may sit at the same indent
+    let mut open_key: Option<usize> = None;
+    // Block scalar: original indent of its key, new body indent, original body indent
+    let mut scalar: Option<(usize, usize, Option<usize>)> = None;
+
+    for &line in &block.lines {
+        let orig = indent_of(line);
+        if let Some((key_orig, body, base)) = scalar {
+            if line.is_empty() {
+                out.push(String::new());
+                continue;
+            }
+            if orig > key_orig {
+                let base = base.unwrap_or(orig);
+                scalar = Some((key_orig, body, Some(base)));
+                let pad = " ".repeat(body + orig.saturating_sub(base));
+                out.push(format!("{}{}", pad, line.trim_start()));
+                continue;
+            }
+            scalar = None;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        if is_comment(line) {
+            comments.push(line.trim());
+            continue;
+        }
+
+        let text = line.trim_start();
+        let item = text == "-" || text.starts_with("- ");
+        while stack
+            .last()
+            .is_some_and(|top| top.orig > orig || (top.orig == orig && top.item && !item))
+        {
+            stack.pop();
+        }
+        let new = match stack.last() {
+            Some(top) if top.orig == orig && item && open_key == Some(orig) && !top.item => {
+                let new = top.child;
+                stack.push(Level {
+                    orig,
+                    new,
+                    child: new + 2,
+                    item: true,
+                });
+                new
+            }
+            Some(top) if top.orig == orig => top.new,
+            parent => {
+                let new = parent.map_or(0, |p| p.child);
+                let child = if item { new + 2 } else { new + options.indent };
+                stack.push(Level {
+                    orig,
+                    new,
+                    child,
+                    item,
+                });
+                new
+            }
+        };
+        if let Some(top) = stack.last_mut().filter(|t| t.orig == orig) {
+            // A list item's own fields line up after the dash
+            top.child = if item { new + 2 } else { new + options.indent };
+        }
+
+        let pad = " ".repeat(new);
+        out.extend(comments.drain(..).map(|c| format!("{}{}", pad, c)));
+        let normalized = normalize_entry(text);
+        let value = entry_value(&normalized);
+        open_key = (value.is_empty() || value.starts_with('#')).then_some(orig);
+        if value.starts_with('|') || value.starts_with('>') {
+            let column = if item { new + 2 } else { new };
+            scalar = Some((orig, column + options.indent, None));
+        }
+        out.push(format!("{}{}", pad, normalized));
+    }
+
+    let pad = " ".repeat(stack.last().map_or(0, |l| l.new));
+    out.extend(comments.drain(..).map(|c| format!("{}{}", pad, c)));
+    while out.last().is_some_and(|l| l.is_empty()) {
+        out.pop();
+    }
+    out
+}
+
+/// Value part of a normalised entry (after `- ` and `key: `)
+fn entry_value(entry: &str) -> &str {
+    let mut text = entry;
+    while let Some(rest) = text.strip_prefix("- ") {
+        text = rest;
+    }
+    match split_key(text) {
+        Some((_, rest)) => rest.trim(),
+        None => text,
+    }
+}
+
+/// `key: value`, `- item` or a bare scalar, with spacing and quoting fixed
+fn normalize_entry(text: &str) -> String {
+    if text == "-" {
+        return text.to_string();
+    }
+    if let Some(rest) = text.strip_prefix("- ") {
+        return format!("- {}", normalize_entry(rest.trim_start()));
+    }
+    match split_key(text) {
+        Some((key, rest)) => {
+            let rest = rest.trim();
+            if rest.is_empty() {
+                format!("{}:", key)
+            } else {
+                let name = unquote(key).unwrap_or(key);
+                format!("{}: {}", key, normalize_scalar(name, rest))
+            }
+        }
+        None => normalize_scalar("", text),
+    }
+}
+
+fn normalize_scalar(key: &str, text: &str) -> String {
+    let (value, comment) = split_comment(text);
+    let bare = unquote(value).unwrap_or(value);
+    let value = if is_percent(bare) {
+        format!("\"{}\"", bare)
+    } else if VERSION_KEYS.contains(&key) && is_version(bare) {
+        if bare.parse::<f64>().is_ok() {
+            format!("\"{}\"", bare)
+        } else {
+            bare.to_string()
+        }
+    } else {
+        value.to_string()
+    };
+    match (value.is_empty(), comment) {
+        (_, None) => value,
+        (true, Some(comment)) => comment.to_string(),
+        (false, Some(comment)) => format!("{} {}", value, comment),
+    }
+}
+
+/// `key` and the rest of the line for `key:` / `key: value` entries
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    // A list item, even when its value contains `: ` (`- "y: z"`)
+    if text == "-" || text.starts_with("- ") {
+        return None;
+    }
+    let end = match text.chars().next()? {
+        quote @ ('"' | '\'') => text[1..].find(quote)? + 2,
+        '[' | '{' | '#' | '&' | '*' | '!' | '|' | '>' => return None,
+        _ => text
+            .find(": ")
+            .or_else(|| text.strip_suffix(':').map(str::len))?,
+    };
+    let rest = text[end..].strip_prefix(':')?;
+    if !(rest.is_empty() || rest.starts_with(' ')) || text[..end].contains(" #") {
+        return None;
+    }
+    Some((&text[..end], rest))
+}
+
+/// Split a scalar from a trailing `# comment`
+fn split_comment(text: &str) -> (&str, Option<&str>) {
+    if text.starts_with('#') {
+        return ("", Some(text));
+    }
+    let from = match text.chars().next() {
+        Some(quote @ ('"' | '\'')) => text[1..].find(quote).map_or(text.len(), |i| i + 2),
+        _ => 0,
+    };
+    match text[from..].find(" #") {
+        Some(i) => (
+            text[..from + i].trim_end(),
+            Some(text[from + i..].trim_start()),
+        ),
+        None => (text, None),
+    }
+}
+
+/// Contents of a simple quoted scalar (no escapes)
+fn unquote(text: &str) -> Option<&str> {
+    let inner = text
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .filter(|t| !t.contains(['"', '\\']))
+        .or_else(|| {
+            text.strip_prefix('\'')
+                .and_then(|t| t.strip_suffix('\''))
+                .filter(|t| !t.contains('\''))
+        })?;
+    Some(inner)
+}
+
+/// `85%`, `92.5%`
+fn is_percent(text: &str) -> bool {
+    text.strip_suffix('%')
+        .is_some_and(|n| !n.is_empty() && n.split('.').count() <= 2 && n.split('.').all(is_digits))
+}
+
+/// `2.5.0`, `1.0`, `2`, `1.0.0-beta.1`
+fn is_version(text: &str) -> bool {
+    let core = text.split(['-', '+']).next().unwrap_or_default();
+    let suffix = &text[core.len()..];
+    core.split('.').all(is_digits)
+        && suffix
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+}
+
+fn is_digits(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const MESSY: &str = r#"# project.faf - hand written
+
+tags: [rust, cli]
+# Where the code lives
+instant_context:
+    key_files:
+    - src/lib.rs     # entry point
+    - src/main.rs
+    commands:
+        test:   cargo test
+
+    # lint before pushing
+        lint: cargo clippy
+project:
+    name: demo
+    version: '1.0'
+    description: |
+        First line
+
+          indented line
+faf_version: '2.5.0'
+ai_score: 85%
+custom_section:
+  anything: goes
+
+# end of file
+"#;
+
+    const EXPECTED: &str = r#"# project.faf - hand written
+
+faf_version: 2.5.0
+
+project:
+  name: demo
+  version: "1.0"
+  description: |
+    First line
+
+      indented line
+
+ai_score: "85%"
+
+# Where the code lives
+instant_context:
+  key_files:
+    - src/lib.rs # entry point
+    - src/main.rs
+  commands:
+    test: cargo test
+    # lint before pushing
+    lint: cargo clippy
+
+tags: [rust, cli]
+
+custom_section:
+  anything: goes
+
+# end of file
+"#;
+
+    #[test]
+    fn test_format_canonical_layout() {
+        let formatted = format(MESSY, &FormatOptions::default()).unwrap();
+        assert_eq!(formatted, EXPECTED);
+        let before: Value = serde_yaml::from_str(MESSY).unwrap();
+        let after: Value = serde_yaml::from_str(&formatted).unwrap();
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        let options = FormatOptions::default();
+        for content in [MESSY, EXPECTED, include_str!("../examples/full.faf")] {
+            let once = format(content, &options).unwrap();
+            assert_eq!(format(&once, &options).unwrap(), once);
+            assert!(is_formatted(&once, &options).unwrap());
+        }
+        assert!(!is_formatted(MESSY, &options).unwrap());
+
+        // stringify output formats to the same canonical text as the source
+        let full = include_str!("../examples/full.faf");
+        let round = crate::stringify(&parse(full).unwrap()).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<Value>(&format(&round, &options).unwrap()).unwrap(),
+            serde_yaml::from_str::<Value>(full).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_format_options_and_errors() {
+        let options = FormatOptions {
+            indent: 4,
+            blank_lines: false,
+        };
+        let formatted = format(EXPECTED, &options).unwrap();
+        assert!(formatted.contains("\ninstant_context:\n    key_files:\n        - src/lib.rs"));
+        assert!(formatted.contains("faf_version: 2.5.0\nproject:"));
+        assert_eq!(format(&formatted, &options).unwrap(), formatted);
+
+        assert!(matches!(
+            format("  ", &options),
+            Err(FafError::EmptyContent)
+        ));
+        assert!(matches!(
+            format("a: [", &options),
+            Err(FafError::YamlError(_))
+        ));
+
+        // Sorting would put the alias in project above its anchor in tags
+        let aliased = "tags: [&name demo]\nfaf_version: 2.5.0\nproject:\n  name: *name\n";
+        assert!(matches!(
+            format(aliased, &options),
+            Err(FafError::CannotFormat(_))
+        ));
+        assert!(is_formatted(aliased, &options).is_err());
+    }
+
+    #[test]
+    fn test_format_zero_indent_lists() {
+        let options = FormatOptions::default();
+        for (content, expected) in [
+            (
+                "tags:\n- x\n- \"y: z\"\n",
+                "tags:\n  - x\n  - \"y: z\"\n",
+            ),
+            (
+                "tags:\n- \"y: z\"\n- x\n",
+                "tags:\n  - \"y: z\"\n  - x\n",
+            ),
+            (
+                "project:\n  name: demo\ntags:\n- 'a: b'\n- c: d\n",
+                "project:\n  name: demo\n\ntags:\n  - 'a: b'\n  - c: d\n",
+            ),
+        ] {
+            assert_eq!(format(content, &options).unwrap(), expected);
+            assert!(!is_formatted(content, &options).unwrap());
+            assert!(is_formatted(expected, &options).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_format_quotes_numeric_versions() {
+        let options = FormatOptions::default();
+        assert_eq!(
+            format("project:\n  name: demo\n  version: 1.10\n", &options).unwrap(),
+            "project:\n  name: demo\n  version: \"1.10\"\n"
+        );
+        assert_eq!(
+            format("project:\n  version: 2\n", &options).unwrap(),
+            "project:\n  version: \"2\"\n"
+        );
+    }
+}
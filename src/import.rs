@@ -0,0 +1,817 @@
+//! Import FAF context from CLAUDE.md, AGENTS.md and README.md
+//!
+//! The reverse of [`export`](crate::export): headings, bullet lists and
+//! shell code fences are read into an [`ImportPatch`] where every proposed
+//! field carries a [`Confidence`]. Text written by the exporter reads back
+//! with high confidence; prose and guesses rank lower.
+
+use std::fs;
+use std::path::Path;
+
+use crate::fix::AppliedFix;
+use crate::parser::FafError;
+use crate::types::{FafData, HumanContext, InstantContext, Preferences, Stack, State};
+
+/// How sure the importer is about a proposed field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// Inferred indirectly (language from command names)
+    Low,
+    /// Read from prose or loosely structured lists
+    Medium,
+    /// Read from a labelled field under a matching heading
+    High,
+}
+
+/// Proposed value for one field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportValue {
+    Text(String),
+    List(Vec<String>),
+}
+
+/// One proposed field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedField {
+    /// Dotted path using YAML names (`stack.database`, `instant_context.commands.test`)
+    pub path: String,
+    pub value: ImportValue,
+    pub confidence: Confidence,
+    /// File and heading the value came from (`AGENTS.md: Commands`)
+    pub source: String,
+}
+
+/// Fields proposed by an import, in the order they were found
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportPatch {
+    pub fields: Vec<ImportedField>,
+}
+
+/// Options for [`ImportPatch::merge_into`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// Replace fields that already have a value (default: keep them)
+    pub overwrite: bool,
+    /// Skip fields below this confidence
+    pub min_confidence: Confidence,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        ImportOptions {
+            overwrite: false,
+            min_confidence: Confidence::Medium,
+        }
+    }
+}
+
+const TEXT_PATHS: &[&str] = &[
+    "project.name",
+    "project.goal",
+    "project.main_language",
+    "project.approach",
+    "project.version",
+    "project.license",
+    "instant_context.what_building",
+    "instant_context.tech_stack",
+    "instant_context.deployment",
+    "stack.frontend",
+    "stack.backend",
+    "stack.database",
+    "stack.infrastructure",
+    "stack.build_tool",
+    "stack.testing",
+    "stack.cicd",
+    "human_context.who",
+    "human_context.what",
+    "human_context.why",
+    "human_context.how",
+    "human_context.where",
+    "human_context.when",
+    "preferences.quality_bar",
+    "preferences.testing",
+    "preferences.documentation",
+    "preferences.code_style",
+    "state.phase",
+    "state.version",
+    "state.focus",
+];
+
+const LIST_PATHS: &[&str] = &["instant_context.key_files", "state.milestones", "tags"];
+
+impl ImportPatch {
+    /// Proposed field at `path`
+    pub fn get(&self, path: &str) -> Option<&ImportedField> {
+        self.fields.iter().find(|f| f.path == path)
+    }
+
+    /// Add a field: lists are extended, text is replaced only by a more
+    /// confident value
+    pub fn push(&mut self, field: ImportedField) {
+        let Some(existing) = self.fields.iter_mut().find(|f| f.path == field.path) else {
+            self.fields.push(field);
+            return;
+        };
+        match (&mut existing.value, field.value) {
+            (ImportValue::List(items), ImportValue::List(new)) => {
+                for item in new {
+                    if !items.contains(&item) {
+                        items.push(item);
+                    }
+                }
+                existing.confidence = existing.confidence.max(field.confidence);
+            }
+            (_, value) if field.confidence > existing.confidence => {
+                existing.value = value;
+                existing.confidence = field.confidence;
+                existing.source = field.source;
+            }
+            _ => {}
+        }
+    }
+
+    /// Combine with another patch (e.g. a second source file)
+    pub fn extend(&mut self, other: ImportPatch) {
+        for field in other.fields {
+            self.push(field);
+        }
+    }
+
+    /// Proposed fields as a fresh `FafData`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use faf_rust_sdk::import_markdown;
+    ///
+    /// let patch = import_markdown("# demo\n\nA tiny CLI.\n", "README.md");
+    /// let data = patch.to_data();
+    /// assert_eq!(data.project.name, "demo");
+    /// assert_eq!(data.project.goal.as_deref(), Some("A tiny CLI."));
+    /// ```
+    pub fn to_data(&self) -> FafData {
+        let mut data = FafData {
+            faf_version: "2.5.0".to_string(),
+            ..Default::default()
+        };
+        let options = ImportOptions {
+            overwrite: true,
+            min_confidence: Confidence::Low,
+        };
+        self.merge_into(&mut data, &options);
+        data
+    }
+
+    /// Apply proposed fields to existing data
+    ///
+    /// Fields that already have a value are kept unless `options.overwrite`
+    /// is set. Returns what changed.
+    pub fn merge_into(&self, data: &mut FafData, options: &ImportOptions) -> Vec<AppliedFix> {
+        let mut applied = Vec::new();
+        for field in &self.fields {
+            if field.confidence < options.min_confidence {
+                continue;
+            }
+            if apply(data, field, options.overwrite) {
+                applied.push(AppliedFix {
+                    rule: None,
+                    path: field.path.clone(),
+                    description: format!(
+                        "imported from {} ({:?} confidence)",
+                        field.source, field.confidence
+                    ),
+                });
+            }
+        }
+        applied
+    }
+}
+
+fn apply(data: &mut FafData, field: &ImportedField, overwrite: bool) -> bool {
+    match &field.value {
+        ImportValue::Text(value) => {
+            if let Some(name) = field.path.strip_prefix("instant_context.commands.") {
+                let commands = &mut data
+                    .instant_context
+                    .get_or_insert_with(InstantContext::default)
+                    .commands;
+                if commands.contains_key(name) && !overwrite {
+                    return false;
+                }
+                commands.insert(name.to_string(), value.clone());
+                return true;
+            }
+            if field.path == "project.name" {
+                if !data.project.name.is_empty() && !overwrite {
+                    return false;
+                }
+                data.project.name = value.clone();
+                return true;
+            }
+            let Some(slot) = text_slot(data, &field.path) else {
+                return false;
+            };
+            if slot.is_some() && !overwrite {
+                return false;
+            }
+            *slot = Some(value.clone());
+            true
+        }
+        ImportValue::List(items) => {
+            let list = match field.path.as_str() {
+                "instant_context.key_files" => {
+                    &mut data
+                        .instant_context
+                        .get_or_insert_with(InstantContext::default)
+                        .key_files
+                }
+                "state.milestones" => &mut data.state.get_or_insert_with(State::default).milestones,
+                "tags" => &mut data.tags,
+                _ => return false,
+            };
+            if !list.is_empty() && !overwrite {
+                return false;
+            }
+            *list = items.clone();
+            true
+        }
+    }
+}
+
+fn text_slot<'a>(data: &'a mut FafData, path: &str) -> Option<&'a mut Option<String>> {
+    let (section, key) = path.split_once('.')?;
+    let slot = match section {
+        "project" => {
+            let p = &mut data.project;
+            match key {
+                "goal" => &mut p.goal,
+                "main_language" => &mut p.main_language,
+                "approach" => &mut p.approach,
+                "version" => &mut p.version,
+                "license" => &mut p.license,
+                _ => return None,
+            }
+        }
+        "instant_context" => {
+            let ic = data
+                .instant_context
+                .get_or_insert_with(InstantContext::default);
+            match key {
+                "what_building" => &mut ic.what_building,
+                "tech_stack" => &mut ic.tech_stack,
+                "deployment" => &mut ic.deployment,
+                _ => return None,
+            }
+        }
+        "stack" => {
+            let s = data.stack.get_or_insert_with(Stack::default);
+            match key {
+                "frontend" => &mut s.frontend,
+                "backend" => &mut s.backend,
+                "database" => &mut s.database,
+                "infrastructure" => &mut s.infrastructure,
+                "build_tool" => &mut s.build_tool,
+                "testing" => &mut s.testing,
+                "cicd" => &mut s.cicd,
+                _ => return None,
+            }
+        }
+        "human_context" => {
+            let h = data.human_context.get_or_insert_with(HumanContext::default);
+            match key {
+                "who" => &mut h.who,
+                "what" => &mut h.what,
+                "why" => &mut h.why_field,
+                "how" => &mut h.how,
+                "where" => &mut h.where_field,
+                "when" => &mut h.when,
+                _ => return None,
+            }
+        }
+        "preferences" => {
+            let pr = data.preferences.get_or_insert_with(Preferences::default);
+            match key {
+                "quality_bar" => &mut pr.quality_bar,
+                "testing" => &mut pr.testing,
+                "documentation" => &mut pr.documentation,
+                "code_style" => &mut pr.code_style,
+                _ => return None,
+            }
+        }
+        "state" => {
+            let st = data.state.get_or_insert_with(State::default);
+            match key {
+                "phase" => &mut st.phase,
+                "version" => &mut st.version,
+                "focus" => &mut st.focus,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    Some(slot)
+}
+
+/// What a heading says about the bullets and fences below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Heading {
+    /// A FAF section title, as written by the exporter (`Instant context`)
+    Faf(&'static str),
+    TechStack,
+    Commands,
+    Files,
+    Other,
+}
+
+fn classify_heading(text: &str) -> Heading {
+    let text = text.trim().to_lowercase();
+    let faf = match text.as_str() {
+        "project" => Some("project"),
+        "instant context" => Some("instant_context"),
+        "stack" => Some("stack"),
+        "human context" => Some("human_context"),
+        "preferences" => Some("preferences"),
+        "state" => Some("state"),
+        "tags" => Some("tags"),
+        _ => None,
+    };
+    let has = |words: &[&str]| words.iter().any(|w| text.contains(w));
+    match faf {
+        Some(section) => Heading::Faf(section),
+        None if has(&["stack", "built with", "technolog", "dependencies"]) => Heading::TechStack,
+        None if has(&[
+            "command",
+            "script",
+            "build",
+            "test",
+            "develop",
+            "usage",
+            "getting started",
+            "setup",
+        ]) =>
+        {
+            Heading::Commands
+        }
+        None if has(&["key files", "structure", "layout", "architecture", "files"]) => {
+            Heading::Files
+        }
+        None => Heading::Other,
+    }
+}
+
+/// `main_language` -> `main_language`, `Key files` -> `key_files`, `CI/CD` -> `cicd`
+fn field_key(label: &str) -> String {
+    label
+        .trim()
+        .to_lowercase()
+        .replace('/', "")
+        .replace([' ', '-'], "_")
+}
+
+/// Split a bullet into label and value (`**Goal:** Ship it`, `Database: PostgreSQL`)
+fn labelled(text: &str) -> Option<(String, String)> {
+    let plain = text.replace("**", "");
+    let (label, value) = plain.split_once(':')?;
+    if label.is_empty() || label.len() > 40 || label.contains('`') {
+        return None;
+    }
+    Some((label.trim().to_string(), value.trim().to_string()))
+}
+
+/// First `code span` in a line
+fn code_span(text: &str) -> Option<&str> {
+    let start = text.find('`')? + 1;
+    let len = text[start..].find('`')?;
+    Some(&text[start..start + len]).filter(|s| !s.trim().is_empty())
+}
+
+/// Conventional command name for a shell line (`cargo test --all` -> `test`)
+fn command_name(command: &str) -> Option<&'static str> {
+    let words: Vec<String> = command
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .map(|w| w.to_lowercase())
+        .collect();
+    let has = |names: &[&str]| words.iter().any(|w| names.contains(&w.as_str()));
+    if has(&["test", "pytest", "jest", "vitest"]) {
+        Some("test")
+    } else if has(&["clippy", "lint", "eslint", "ruff", "golangci-lint"]) {
+        Some("lint")
+    } else if has(&["fmt", "format", "prettier", "black"]) {
+        Some("format")
+    } else if has(&["bench"]) {
+        Some("bench")
+    } else if has(&["build", "compile"]) {
+        Some("build")
+    } else if has(&["install", "setup"]) {
+        Some("install")
+    } else if has(&["dev", "serve", "start", "watch"]) {
+        Some("dev")
+    } else {
+        None
+    }
+}
+
+fn language_for(command: &str) -> Option<&'static str> {
+    match command.split_whitespace().next()? {
+        "cargo" | "rustc" => Some("Rust"),
+        "go" => Some("Go"),
+        "npm" | "npx" | "pnpm" | "yarn" | "bun" | "node" => Some("JavaScript"),
+        "python" | "python3" | "pip" | "pytest" | "poetry" | "uv" => Some("Python"),
+        "mvn" | "gradle" | "./gradlew" => Some("Java"),
+        "bundle" | "rake" => Some("Ruby"),
+        "dotnet" => Some("C#"),
+        _ => None,
+    }
+}
+
+fn is_shell(lang: &str) -> bool {
+    matches!(
+        lang,
+        "" | "sh" | "bash" | "shell" | "console" | "zsh" | "powershell"
+    )
+}
+
+struct Importer<'a> {
+    file: &'a str,
+    heading_text: String,
+    heading: Heading,
+    /// List field awaiting nested bullets (`- **Key files:**` then `  - a`)
+    pending: Option<String>,
+    patch: ImportPatch,
+}
+
+impl Importer<'_> {
+    fn add(&mut self, path: &str, value: ImportValue, confidence: Confidence) {
+        let source = if self.heading_text.is_empty() {
+            self.file.to_string()
+        } else {
+            format!("{}: {}", self.file, self.heading_text)
+        };
+        self.patch.push(ImportedField {
+            path: path.to_string(),
+            value,
+            confidence,
+            source,
+        });
+    }
+
+    fn text(&mut self, path: &str, value: &str, confidence: Confidence) {
+        let value = value.trim().trim_matches('`').trim();
+        if !value.is_empty() {
+            self.add(path, ImportValue::Text(value.to_string()), confidence);
+        }
+    }
+
+    fn command(&mut self, line: &str, confidence: Confidence) {
+        let command = line.trim().trim_start_matches("$ ").trim();
+        if command.is_empty() || command.starts_with('#') {
+            return;
+        }
+        if let Some(name) = command_name(command) {
+            self.text(
+                &format!("instant_context.commands.{}", name),
+                command,
+                confidence,
+            );
+        }
+        if let Some(language) = language_for(command) {
+            self.text("project.main_language", language, Confidence::Low);
+        }
+    }
+
+    fn nested(&mut self, path: &str, item: &str) {
+        if path == "instant_context.commands" {
+            if let Some((name, command)) = item.split_once(':') {
+                let name = name.trim().trim_matches('`');
+                self.text(&format!("{}.{}", path, name), command, Confidence::High);
+            }
+        } else {
+            let item = item.trim().trim_matches('`').to_string();
+            self.add(path, ImportValue::List(vec![item]), Confidence::High);
+        }
+    }
+
+    fn bullet(&mut self, text: &str) {
+        match self.heading {
+            Heading::Faf("tags") => self.add(
+                "tags",
+                ImportValue::List(vec![text.to_string()]),
+                Confidence::High,
+            ),
+            Heading::Faf(section) => {
+                let Some((label, value)) = labelled(text) else {
+                    return;
+                };
+                let path = format!("{}.{}", section, field_key(&label));
+                if value.is_empty() {
+                    let is_list =
+                        LIST_PATHS.contains(&path.as_str()) || path == "instant_context.commands";
+                    self.pending = is_list.then_some(path);
+                } else if TEXT_PATHS.contains(&path.as_str()) {
+                    self.text(&path, &value, Confidence::High);
+                }
+            }
+            Heading::TechStack => match labelled(text) {
+                Some((label, value))
+                    if TEXT_PATHS.contains(&format!("stack.{}", field_key(&label)).as_str()) =>
+                {
+                    self.text(
+                        &format!("stack.{}", field_key(&label)),
+                        &value,
+                        Confidence::High,
+                    )
+                }
+                _ => {
+                    let item = text.replace("**", "");
+                    let item = item.split(" - ").next().unwrap_or_default().trim();
+                    self.add(
+                        "instant_context.tech_stack",
+                        ImportValue::List(vec![item.to_string()]),
+                        Confidence::High,
+                    );
+                }
+            },
+            Heading::Commands => {
+                if let Some(command) = code_span(text) {
+                    let command = command.to_string();
+                    self.command(&command, Confidence::Medium);
+                }
+            }
+            Heading::Files => {
+                let file = code_span(text)
+                    .unwrap_or_else(|| text.split_whitespace().next().unwrap_or_default());
+                if file.contains('/') || file.contains('.') {
+                    let file = file.to_string();
+                    self.add(
+                        "instant_context.key_files",
+                        ImportValue::List(vec![file]),
+                        Confidence::Medium,
+                    );
+                }
+            }
+            Heading::Other => {}
+        }
+    }
+}
+
+/// Read proposed fields from Markdown (CLAUDE.md, AGENTS.md, README.md)
+///
+/// `source` names the file in each field's [`ImportedField::source`].
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{import_markdown, Confidence, ImportValue};
+///
+/// let md = "# demo\n\nA tiny CLI.\n\n## Commands\n\n```bash\ncargo test\n```\n";
+/// let patch = import_markdown(md, "AGENTS.md");
+/// let test = patch.get("instant_context.commands.test").unwrap();
+/// assert_eq!(test.value, ImportValue::Text("cargo test".into()));
+/// assert_eq!(test.confidence, Confidence::High);
+/// ```
+pub fn import_markdown(content: &str, source: &str) -> ImportPatch {
+    let mut importer = Importer {
+        file: source,
+        heading_text: String::new(),
+        heading: Heading::Other,
+        pending: None,
+        patch: ImportPatch::default(),
+    };
+    let mut fence: Option<String> = None;
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut described = false;
+    let mut level = 0;
+
+    for line in content.lines().chain(std::iter::once("")) {
+        let trimmed = line.trim();
+
+        if let Some(lang) = &fence {
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fence = None;
+            } else if is_shell(lang) {
+                let confidence = match importer.heading {
+                    Heading::Commands => Confidence::High,
+                    _ => Confidence::Medium,
+                };
+                importer.command(line, confidence);
+            }
+            continue;
+        }
+
+        // A paragraph ends at any non-text line
+        let is_text = !trimmed.is_empty()
+            && !trimmed.starts_with(['#', '-', '*', '+', '`', '~', '<', '!', '[', '|', '>']);
+        if is_text && level <= 1 && !described {
+            paragraph.push(trimmed);
+            continue;
+        }
+        if !paragraph.is_empty() {
+            let text = paragraph.join(" ");
+            importer.text("project.goal", &text, Confidence::Medium);
+            importer.text("instant_context.what_building", &text, Confidence::Low);
+            paragraph.clear();
+            described = true;
+        }
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(trimmed.trim_start_matches(['`', '~']).trim().to_lowercase());
+            importer.pending = None;
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            let text = trimmed[hashes..].trim().trim_end_matches('#').trim();
+            level = hashes;
+            if hashes == 1 && importer.patch.get("project.name").is_none() && !text.contains(".md")
+            {
+                importer.text("project.name", text, Confidence::Medium);
+            }
+            importer.heading_text = text.to_string();
+            importer.heading = classify_heading(text);
+            importer.pending = None;
+            continue;
+        }
+
+        let indented = line.starts_with([' ', '\t']);
+        let bullet = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|b| trimmed.strip_prefix(b));
+        match (bullet, &importer.pending) {
+            (Some(item), Some(path)) if indented => {
+                let path = path.clone();
+                importer.nested(&path, item);
+            }
+            (Some(item), _) => {
+                importer.pending = None;
+                importer.bullet(item.trim());
+            }
+            (None, _) if !trimmed.is_empty() => importer.pending = None,
+            _ => {}
+        }
+    }
+
+    // Stack bullets collect as a list; the FAF field is one line
+    let mut patch = importer.patch;
+    for field in &mut patch.fields {
+        if field.path == "instant_context.tech_stack" {
+            if let ImportValue::List(items) = &field.value {
+                field.value = ImportValue::Text(items.join(", "));
+            }
+        }
+    }
+    patch
+}
+
+/// Import every instruction file found in `root`
+///
+/// Reads AGENTS.md, CLAUDE.md and README.md in that order; for each field
+/// the most confident source wins.
+pub fn import_dir<P: AsRef<Path>>(root: P) -> Result<ImportPatch, FafError> {
+    let mut patch = ImportPatch::default();
+    for name in ["AGENTS.md", "CLAUDE.md", "README.md"] {
+        let path = root.as_ref().join(name);
+        if path.is_file() {
+            patch.extend(import_markdown(&fs::read_to_string(&path)?, name));
+        }
+    }
+    Ok(patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{export, parse, ExportTarget};
+
+    const AGENTS: &str = r#"# rocket
+
+Rocket is a launch scheduler for small satellites.
+
+## Tech Stack
+
+- Rust 1.80
+- **Database:** PostgreSQL
+- Tokio - async runtime
+
+## Development commands
+
+```bash
+$ cargo build --release
+cargo test --workspace
+# run the linter
+cargo clippy -- -D warnings
+```
+
+## Project structure
+
+- `src/main.rs` - entry point
+- `migrations/` - SQL migrations
+"#;
+
+    #[test]
+    fn test_import_agents_md() {
+        let patch = import_markdown(AGENTS, "AGENTS.md");
+        let text = |path: &str| match &patch.get(path).unwrap().value {
+            ImportValue::Text(t) => t.clone(),
+            ImportValue::List(l) => l.join("|"),
+        };
+
+        assert_eq!(text("project.name"), "rocket");
+        assert_eq!(
+            text("project.goal"),
+            "Rocket is a launch scheduler for small satellites."
+        );
+        assert_eq!(text("instant_context.tech_stack"), "Rust 1.80, Tokio");
+        assert_eq!(text("stack.database"), "PostgreSQL");
+        assert_eq!(
+            text("instant_context.commands.build"),
+            "cargo build --release"
+        );
+        assert_eq!(
+            text("instant_context.commands.test"),
+            "cargo test --workspace"
+        );
+        assert_eq!(
+            text("instant_context.commands.lint"),
+            "cargo clippy -- -D warnings"
+        );
+        assert_eq!(text("instant_context.key_files"), "src/main.rs|migrations/");
+
+        let language = patch.get("project.main_language").unwrap();
+        assert_eq!(language.value, ImportValue::Text("Rust".into()));
+        assert_eq!(language.confidence, Confidence::Low);
+        assert_eq!(
+            patch.get("stack.database").unwrap().source,
+            "AGENTS.md: Tech Stack"
+        );
+    }
+
+    #[test]
+    fn test_round_trip_from_export() {
+        let faf = parse(include_str!("../examples/full.faf")).unwrap();
+        let md = export(&faf, ExportTarget::ClaudeMd);
+        let data = import_markdown(&md, "CLAUDE.md").to_data();
+
+        assert_eq!(data.project.name, "full-example");
+        assert_eq!(data.project.goal, faf.data.project.goal);
+        assert_eq!(
+            data.stack.as_ref().unwrap().cicd.as_deref(),
+            Some("GitHub Actions")
+        );
+        assert_eq!(
+            data.human_context.as_ref().unwrap().why_field,
+            faf.data.human_context.as_ref().unwrap().why_field
+        );
+        let ic = data.instant_context.unwrap();
+        assert_eq!(
+            ic.key_files,
+            faf.data.instant_context.as_ref().unwrap().key_files
+        );
+        assert_eq!(
+            ic.commands,
+            faf.data.instant_context.as_ref().unwrap().commands
+        );
+        assert_eq!(data.state.unwrap().milestones.len(), 3);
+        assert_eq!(data.tags, faf.data.tags);
+    }
+
+    #[test]
+    fn test_merge_keeps_human_fields() {
+        let mut faf =
+            parse("faf_version: 2.5.0\nproject:\n  name: mine\n  goal: Hand written").unwrap();
+        let patch = import_markdown(AGENTS, "AGENTS.md");
+
+        let applied = patch.merge_into(&mut faf.data, &ImportOptions::default());
+        assert_eq!(faf.data.project.name, "mine");
+        assert_eq!(faf.data.project.goal.as_deref(), Some("Hand written"));
+        assert!(faf.data.project.main_language.is_none()); // low confidence skipped
+        assert!(applied.iter().any(|a| a.path == "stack.database"));
+        assert!(!applied.iter().any(|a| a.path == "project.goal"));
+
+        let options = ImportOptions {
+            overwrite: true,
+            ..Default::default()
+        };
+        patch.merge_into(&mut faf.data, &options);
+        assert_eq!(faf.data.project.name, "rocket");
+    }
+
+    #[test]
+    fn test_import_dir_prefers_confident_source() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("README.md"),
+            "# rocket\n\n## Testing\n\n- `cargo test --all`\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("AGENTS.md"), AGENTS).unwrap();
+
+        let patch = import_dir(dir.path()).unwrap();
+        let test = patch.get("instant_context.commands.test").unwrap();
+        assert_eq!(
+            test.value,
+            ImportValue::Text("cargo test --workspace".into())
+        );
+        assert_eq!(test.source, "AGENTS.md: Development commands");
+    }
+}
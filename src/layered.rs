@@ -0,0 +1,373 @@
+//! Hierarchical FAF files: merge package files over their parents
+//!
+//! Every FAF file from the start directory up to the repository root (or
+//! another [`StopMarker`](crate::StopMarker)) is a layer; a file may also pull
+//! in others with `extends: ../project.faf` (a path or a list of paths,
+//! relative to the file). Layers are deep-merged section by section with the
+//! nearest file winning. Lists are replaced, not concatenated.
+
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::discovery::{DiscoveryOptions, FindError};
+use crate::parser::{FafError, FafFile};
+
+/// Key naming files to inherit from
+const EXTENDS: &str = "extends";
+
+/// The merged result of every layer
+#[derive(Debug, Clone)]
+pub struct LayeredFaf {
+    /// Effective data; `path` is the nearest file
+    pub faf: FafFile,
+    /// Files that contributed, highest priority first
+    pub layers: Vec<PathBuf>,
+    /// File each effective value came from, by dotted path
+    pub provenance: BTreeMap<String, PathBuf>,
+}
+
+impl LayeredFaf {
+    /// File the effective value at `path` came from
+    ///
+    /// Lists count as one value (`tags`, `instant_context.key_files`).
+    pub fn source_of(&self, path: &str) -> Option<&Path> {
+        self.provenance.get(path).map(PathBuf::as_path)
+    }
+}
+
+/// Find every FAF file up to the repository root and merge them
+///
+/// Searches from `start_dir` (default: current directory) up to the first
+/// directory containing `.git`. Outside a repository only the nearest file
+/// is used, so a stray `project.faf` in a home directory is never merged in.
+/// Paths in the result are canonical.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::find_and_parse_layered;
+///
+/// let layered = find_and_parse_layered(Some("packages/api")).unwrap();
+/// println!("Project: {}", layered.faf.project_name());
+/// if let Some(file) = layered.source_of("preferences.testing") {
+///     println!("testing preference from {}", file.display());
+/// }
+/// ```
+pub fn find_and_parse_layered<P: AsRef<Path>>(
+    start_dir: Option<P>,
+) -> Result<LayeredFaf, FindError> {
+    find_and_parse_layered_with(start_dir, &DiscoveryOptions::new().stop_at_repo_root())
+}
+
+/// [`find_and_parse_layered`] with custom file names, depth and stop markers
+///
+/// Layers are collected up to the first directory matching a stop marker;
+/// if none is reached within the depth limit (or there are no markers),
+/// only the nearest file is used. The environment override is not
+/// consulted.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{find_and_parse_layered_with, DiscoveryOptions};
+///
+/// let options = DiscoveryOptions::new().stop_at_repo_root().stop_at_cargo_workspace();
+/// let layered = find_and_parse_layered_with(Some("crates/core"), &options).unwrap();
+/// println!("{} layers", layered.layers.len());
+/// ```
+pub fn find_and_parse_layered_with<P: AsRef<Path>>(
+    start_dir: Option<P>,
+    options: &DiscoveryOptions,
+) -> Result<LayeredFaf, FindError> {
+    let start = match start_dir {
+        Some(p) => p.as_ref().to_path_buf(),
+        None => env::current_dir().map_err(|e| FindError::Unreadable(PathBuf::from("."), e))?,
+    };
+    let start = fs::canonicalize(&start).map_err(|e| FindError::Unreadable(start, e))?;
+
+    let mut unreadable = Vec::new();
+    let files = options.layers_from(&start, &mut unreadable);
+    if let Some(entry) = unreadable.into_iter().next() {
+        return Err(FindError::Unreadable(
+            entry.path,
+            io::Error::from(entry.kind),
+        ));
+    }
+    let mut loader = Loader::default();
+    for file in files {
+        loader.expand(file)?;
+    }
+    if loader.layers.is_empty() {
+        return Err(FindError::NotFound);
+    }
+
+    let mut merged = Value::Mapping(Mapping::new());
+    let mut provenance = BTreeMap::new();
+    for (path, value) in loader.layers.iter().rev() {
+        overlay(&mut merged, value, "", path, &mut provenance);
+    }
+    let data = serde_yaml::from_value(merged)
+        .map_err(|e| FindError::ParseError(FafError::YamlError(e)))?;
+
+    let layers: Vec<PathBuf> = loader.layers.into_iter().map(|(p, _)| p).collect();
+    let mut faf = FafFile::new(data);
+    faf.path = Some(layers[0].to_string_lossy().to_string());
+    Ok(LayeredFaf {
+        faf,
+        layers,
+        provenance,
+    })
+}
+
+#[derive(Default)]
+struct Loader {
+    /// Loaded files, highest priority first, with `extends` removed
+    layers: Vec<(PathBuf, Value)>,
+    /// Chain of `extends` being followed, for cycle detection
+    chain: Vec<PathBuf>,
+}
+
+impl Loader {
+    /// Add `path`, then the files it extends (at lower priority)
+    fn expand(&mut self, path: PathBuf) -> Result<(), FindError> {
+        if let Some(at) = self.chain.iter().position(|p| *p == path) {
+            let mut cycle = self.chain[at..].to_vec();
+            cycle.push(path);
+            return Err(FindError::ExtendsCycle(cycle));
+        }
+        if self.layers.iter().any(|(p, _)| *p == path) {
+            return Ok(());
+        }
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => return Err(FindError::Unreadable(path, e)),
+        };
+        let mut value: Value = serde_yaml::from_str(&content)
+            .map_err(|e| FindError::ParseError(FafError::YamlError(e)))?;
+        let extends = match value.as_mapping_mut().and_then(|m| m.remove(EXTENDS)) {
+            Some(Value::String(target)) => vec![target],
+            Some(Value::Sequence(targets)) => targets
+                .into_iter()
+                .filter_map(|t| t.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.layers.push((path.clone(), value));
+
+        let dir = path.parent().unwrap_or(Path::new("/")).to_path_buf();
+        self.chain.push(path);
+        for target in extends {
+            let target = fs::canonicalize(dir.join(&target))
+                .map_err(|_| FindError::ExtendsNotFound(dir.join(&target)))?;
+            self.expand(target)?;
+        }
+        self.chain.pop();
+        Ok(())
+    }
+}
+
+/// Deep-merge `layer` over `base`, recording the source of each leaf
+fn overlay(
+    base: &mut Value,
+    layer: &Value,
+    path: &str,
+    source: &Path,
+    provenance: &mut BTreeMap<String, PathBuf>,
+) {
+    if layer.is_null() {
+        return;
+    }
+    if let (Value::Mapping(base), Value::Mapping(layer)) = (&mut *base, layer) {
+        for (key, value) in layer {
+            let Some(name) = key.as_str() else {
+                continue;
+            };
+            let child = if path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", path, name)
+            };
+            let slot = base.entry(key.clone()).or_insert(Value::Null);
+            overlay(slot, value, &child, source, provenance);
+        }
+        return;
+    }
+
+    // Replaced wholesale: forget the sources of anything it held
+    let prefix = format!("{}.", path);
+    provenance.retain(|p, _| p != path && !p.starts_with(&prefix));
+    if layer.is_mapping() {
+        *base = Value::Mapping(Mapping::new());
+        overlay(base, layer, path, source, provenance);
+    } else {
+        *base = layer.clone();
+        provenance.insert(path.to_string(), source.to_path_buf());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const ROOT: &str = r#"
+faf_version: 2.5.0
+project:
+  name: org
+  goal: Shared goal
+stack:
+  cicd: GitHub Actions
+  testing: Jest
+human_context:
+  who: Platform team
+preferences:
+  testing: required
+tags: [org]
+"#;
+
+    const PACKAGE: &str = r#"
+faf_version: 2.5.0
+project:
+  name: api
+stack:
+  backend: Rust
+  testing: cargo test
+tags: [api, rust]
+"#;
+
+    fn tree() -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let root = fs::canonicalize(dir.path()).unwrap();
+        let package = root.join("packages/api");
+        fs::create_dir_all(&package).unwrap();
+        fs::create_dir(root.join(".git")).unwrap();
+        fs::write(root.join("project.faf"), ROOT).unwrap();
+        fs::write(package.join("project.faf"), PACKAGE).unwrap();
+        (dir, root)
+    }
+
+    #[test]
+    fn test_nearest_file_wins() {
+        let (_dir, root) = tree();
+        let package = root.join("packages/api");
+        let layered = find_and_parse_layered(Some(&package)).unwrap();
+        let data = &layered.faf.data;
+
+        assert_eq!(layered.faf.project_name(), "api");
+        assert_eq!(data.project.goal.as_deref(), Some("Shared goal"));
+        let stack = data.stack.as_ref().unwrap();
+        assert_eq!(stack.backend.as_deref(), Some("Rust"));
+        assert_eq!(stack.testing.as_deref(), Some("cargo test"));
+        assert_eq!(stack.cicd.as_deref(), Some("GitHub Actions"));
+        assert_eq!(
+            data.human_context.as_ref().unwrap().who.as_deref(),
+            Some("Platform team")
+        );
+        assert_eq!(data.tags, ["api", "rust"]);
+
+        assert_eq!(
+            layered.layers,
+            [package.join("project.faf"), root.join("project.faf")]
+        );
+        assert_eq!(
+            layered.source_of("stack.cicd"),
+            Some(root.join("project.faf").as_path())
+        );
+        assert_eq!(
+            layered.source_of("stack.testing"),
+            Some(package.join("project.faf").as_path())
+        );
+        assert_eq!(
+            layered.source_of("tags"),
+            Some(package.join("project.faf").as_path())
+        );
+    }
+
+    #[test]
+    fn test_extends_and_repo_boundary() {
+        let (_dir, root) = tree();
+        // A file above the repository root is never a layer
+        let outer = root.join("outer");
+        fs::create_dir_all(outer.join("repo/.git")).unwrap();
+        fs::write(outer.join("project.faf"), ROOT.replace("org", "outside")).unwrap();
+        fs::write(outer.join("repo/project.faf"), PACKAGE).unwrap();
+        let layered = find_and_parse_layered(Some(outer.join("repo"))).unwrap();
+        assert_eq!(layered.layers.len(), 1);
+        assert!(layered.faf.data.human_context.is_none());
+
+        // ...unless pulled in explicitly
+        fs::write(
+            outer.join("repo/project.faf"),
+            format!("extends: ../project.faf\n{}", PACKAGE),
+        )
+        .unwrap();
+        let layered = find_and_parse_layered(Some(outer.join("repo"))).unwrap();
+        assert_eq!(layered.layers[1], outer.join("project.faf"));
+        assert_eq!(
+            layered.faf.data.project.goal.as_deref(),
+            Some("Shared goal")
+        );
+    }
+
+    #[test]
+    fn test_extends_errors() {
+        let (_dir, root) = tree();
+        let shared = root.join("shared.faf");
+        fs::write(
+            &shared,
+            format!("extends: packages/api/project.faf\n{}", ROOT),
+        )
+        .unwrap();
+        fs::write(
+            root.join("packages/api/project.faf"),
+            format!("extends: ../../shared.faf\n{}", PACKAGE),
+        )
+        .unwrap();
+        let err = find_and_parse_layered(Some(root.join("packages/api"))).unwrap_err();
+        assert!(matches!(&err, FindError::ExtendsCycle(chain) if chain.len() == 3));
+
+        fs::write(
+            root.join("project.faf"),
+            format!("extends: [missing.faf]\n{}", ROOT),
+        )
+        .unwrap();
+        let err = find_and_parse_layered(Some(&root)).unwrap_err();
+        assert!(matches!(err, FindError::ExtendsNotFound(p) if p.ends_with("missing.faf")));
+
+        let empty = TempDir::new().unwrap();
+        fs::create_dir(empty.path().join(".git")).unwrap();
+        assert!(matches!(
+            find_and_parse_layered(Some(empty.path())),
+            Err(FindError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn test_layers_need_a_boundary() {
+        let (_dir, root) = tree();
+        let package = root.join("packages/api");
+
+        // No repository root above: the parent file is not merged
+        fs::remove_dir(root.join(".git")).unwrap();
+        let layered = find_and_parse_layered(Some(&package)).unwrap();
+        assert_eq!(layered.layers, [package.join("project.faf")]);
+        assert!(layered.faf.data.human_context.is_none());
+
+        // ...unless another stop marker bounds the walk
+        fs::write(root.join("pnpm-workspace.yaml"), "").unwrap();
+        let options =
+            DiscoveryOptions::new().stop_at(crate::StopMarker::Entry("pnpm-workspace.yaml".into()));
+        let layered = find_and_parse_layered_with(Some(&package), &options).unwrap();
+        assert_eq!(layered.layers.len(), 2);
+
+        assert!(matches!(
+            find_and_parse_layered(Some(root.join("missing"))),
+            Err(FindError::Unreadable(p, e)) if p.ends_with("missing") && e.kind() == io::ErrorKind::NotFound
+        ));
+    }
+}
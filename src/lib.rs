@@ -21,14 +21,69 @@
 mod parser;
 mod types;
 mod validator;
+mod rules;
+mod scoring;
 mod compress;
+mod tokens;
+mod render;
+mod export;
+mod import;
+mod sync;
+mod diff;
+mod patch;
+mod query;
+mod fafb;
+mod format;
+mod fingerprint;
 mod discovery;
+mod layered;
+mod cargo;
+mod fix;
+mod write;
+#[cfg(feature = "watch")]
+mod watch;
 
 pub use parser::{parse, parse_file, stringify, FafFile, FafError};
 pub use types::*;
-pub use validator::{validate, ValidationResult};
-pub use compress::{compress, estimate_tokens, CompressionLevel};
-pub use discovery::{find_faf_file, find_and_parse, FindError};
+pub use validator::{
+    calculate_score, validate, validate_against_fs, Diagnostic, Finding, Rule, RuleContext,
+    RuleLevel, Severity, ValidationResult, Validator, ValidatorConfig,
+};
+pub use scoring::{slots_filled, ScoreBreakdown, ScoringModel, SlotScore, SlotWeight, SLOTS};
+pub use compress::{
+    compress, compress_to_budget, compress_with_profile, estimate_tokens, CompressedFaf,
+    CompressionLevel, CompressionProfile, Omitted,
+};
+pub use tokens::{
+    estimate_tokens_for, estimate_tokens_with, estimate_value_tokens, token_breakdown,
+    HeuristicTokenizer, SectionTokens, TokenBreakdown, Tokenizer,
+};
+pub use render::{render, Format, RenderOptions};
+pub use export::{export, export_merge, export_to, ExportTarget, EXPORT_BEGIN, EXPORT_END};
+pub use import::{
+    import_dir, import_markdown, Confidence, ImportOptions, ImportPatch, ImportValue, ImportedField,
+};
+pub use sync::{merge3, Conflict, MergeResult, Resolution, SyncEngine};
+pub use diff::{diff, Change, FafDiff};
+pub use patch::{apply_patch, Patch, PatchOp};
+pub use query::FieldValue;
+pub use format::{format, is_formatted, FormatOptions};
+pub use fingerprint::{FingerprintOptions, VOLATILE_FIELDS};
+pub use fafb::{
+    compile_fafb, FafBinary, FafbHeader, FafbSection, FAFB_MAGIC, FAFB_VERSION, SECTION_COMMANDS,
+    SECTION_CONTEXT, SECTION_KEY_FILES, SECTION_META, SECTION_TECH_STACK,
+};
+pub use discovery::{
+    discover_all, find_faf_file, find_faf_file_detailed, find_faf_file_with, find_and_parse,
+    DiscoverOptions, DiscoveryOptions, DiscoveryReport, FafLocation, FafMatch, FindError,
+    MatchRule, StopMarker, Unreadable, FAF_PATH_ENV,
+};
+pub use layered::{find_and_parse_layered, find_and_parse_layered_with, LayeredFaf};
+pub use fix::{fix, update_declared_scores, AppliedFix, FixOptions};
+pub use write::{write_atomic, write_file, WriteOptions};
+pub use cargo::{detect_cargo, enrich_from_cargo, CargoCrate, CargoError, CargoProject};
+#[cfg(feature = "watch")]
+pub use watch::{watch, watch_with, FafEvent, FafWatcher, WatchHandle, WatchOptions};
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
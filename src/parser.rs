@@ -1,5 +1,6 @@
 //! Core FAF parser - optimized for inference workloads
 
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::Path;
 use thiserror::Error;
@@ -8,6 +9,7 @@ use crate::types::FafData;
 
 /// FAF parsing errors
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum FafError {
     #[error("Empty content")]
     EmptyContent,
@@ -15,11 +17,35 @@ pub enum FafError {
     #[error("Invalid YAML: {0}")]
     YamlError(#[from] serde_yaml::Error),
 
+    #[error("Invalid JSON: {0}")]
+    JsonError(#[from] serde_json::Error),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
     #[error("Missing required field: {0}")]
     MissingField(String),
+
+    #[error("Unknown validation rule: {0}")]
+    UnknownRule(String),
+
+    #[error("Unmatched faf:begin/faf:end markers in {0}")]
+    InvalidMarkers(String),
+
+    #[error("Invalid patch: {0}")]
+    InvalidPatch(String),
+
+    #[error("Invalid .fafb: {0}")]
+    InvalidFafb(String),
+
+    #[error("Invalid scoring model: {0}")]
+    InvalidScoringModel(String),
+
+    #[error("Cannot format: {0}")]
+    CannotFormat(String),
+
+    #[error("File changed since it was read: {0}")]
+    ChangedOnDisk(String),
 }
 
 /// Parsed FAF file with convenient accessors
@@ -29,9 +55,33 @@ pub struct FafFile {
     pub data: FafData,
     /// Original file path (if loaded from file)
     pub path: Option<String>,
+    /// SHA-256 of the file this was read from, for compare-and-swap writes
+    content_hash: Option<[u8; 32]>,
 }
 
 impl FafFile {
+    /// Wrap data that was not read from a file
+    pub fn new(data: FafData) -> Self {
+        FafFile {
+            data,
+            path: None,
+            content_hash: None,
+        }
+    }
+
+    /// SHA-256 of the file content as last read or written
+    ///
+    /// Set by [`parse_file`]; `None` for content that never came from disk.
+    /// [`write_file`](crate::write_file) checks it when comparing and swapping.
+    pub fn content_hash(&self) -> Option<[u8; 32]> {
+        self.content_hash
+    }
+
+    /// Record the hash of the content now on disk (e.g. from [`write_file`](crate::write_file))
+    pub fn set_content_hash(&mut self, hash: Option<[u8; 32]>) {
+        self.content_hash = hash;
+    }
+
     /// Get project name
     #[inline]
     pub fn project_name(&self) -> &str {
@@ -106,14 +156,14 @@ impl FafFile {
 /// assert_eq!(faf.project_name(), "test");
 /// ```
 pub fn parse(content: &str) -> Result<FafFile, FafError> {
-    let content = content.trim();
-    if content.is_empty() {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
         return Err(FafError::EmptyContent);
     }
 
-    let data: FafData = serde_yaml::from_str(content)?;
+    let data: FafData = serde_yaml::from_str(trimmed)?;
 
-    Ok(FafFile { data, path: None })
+    Ok(FafFile::new(data))
 }
 
 /// Parse FAF from file path
@@ -132,10 +182,16 @@ pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<FafFile, FafError> {
 
     let mut faf = parse(&content)?;
     faf.path = Some(path_str);
+    faf.content_hash = Some(content_hash(&content));
 
     Ok(faf)
 }
 
+/// SHA-256 of file content, as returned by [`FafFile::content_hash`]
+pub(crate) fn content_hash(content: &str) -> [u8; 32] {
+    Sha256::digest(content.as_bytes()).into()
+}
+
 /// Serialize FAF back to YAML string
 pub fn stringify(faf: &FafFile) -> Result<String, FafError> {
     Ok(serde_yaml::to_string(&faf.data)?)
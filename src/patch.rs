@@ -0,0 +1,394 @@
+//! Apply JSON Patch (RFC 6902) and JSON Merge Patch (RFC 7386) to FafData
+//!
+//! Paths may be JSON Pointers (`/human_context/why`) or dotted paths
+//! (`human_context.why`, `instant_context.key_files.0`), both using the
+//! YAML field names. `why_field` and `where_field` are accepted as aliases.
+//!
+//! A patch is applied to a copy and checked against the FAF schema before
+//! anything changes: unknown fields, wrong types or a failed `test` reject
+//! the whole patch.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::parser::FafError;
+use crate::types::FafData;
+
+/// One RFC 6902 operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+/// A patch to apply with [`apply_patch`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Patch {
+    /// RFC 6902 operations, applied in order
+    Json(Vec<PatchOp>),
+    /// RFC 7386 merge patch: objects merge, `null` removes
+    Merge(Value),
+}
+
+impl Patch {
+    /// Parse a patch document: an array is a JSON Patch, an object a merge patch
+    pub fn from_json(content: &str) -> Result<Self, FafError> {
+        match serde_json::from_str(content)? {
+            Value::Array(ops) => Ok(Patch::Json(serde_json::from_value(Value::Array(ops))?)),
+            object @ Value::Object(_) => Ok(Patch::Merge(object)),
+            _ => Err(FafError::InvalidPatch(
+                "expected an array of operations or a merge object".to_string(),
+            )),
+        }
+    }
+}
+
+/// Apply a patch, all or nothing
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{apply_patch, parse, Patch};
+///
+/// let mut faf = parse("faf_version: 2.5.0\nproject:\n  name: app").unwrap();
+/// let patch = Patch::from_json(r#"[
+///     {"op": "add", "path": "human_context.why", "value": "Fewer lost edits"},
+///     {"op": "add", "path": "/tags/-", "value": "rust"}
+/// ]"#).unwrap();
+/// apply_patch(&mut faf.data, &patch).unwrap();
+/// assert_eq!(faf.data.tags, vec!["rust"]);
+///
+/// // Wrong type: rejected, nothing changes
+/// let bad = Patch::from_json(r#"{"project": {"goal": 42}}"#).unwrap();
+/// assert!(apply_patch(&mut faf.data, &bad).is_err());
+/// ```
+pub fn apply_patch(data: &mut FafData, patch: &Patch) -> Result<(), FafError> {
+    let mut doc = serde_json::to_value(&*data)?;
+    match patch {
+        Patch::Json(ops) => {
+            for (i, op) in ops.iter().enumerate() {
+                apply_op(&mut doc, op)
+                    .map_err(|e| FafError::InvalidPatch(format!("operation {}: {}", i, e)))?;
+            }
+        }
+        Patch::Merge(patch) => merge_patch(&mut doc, &normalize_merge(patch)),
+    }
+
+    let patched: FafData =
+        serde_json::from_value(doc.clone()).map_err(|e| FafError::InvalidPatch(e.to_string()))?;
+    let round_trip = serde_json::to_value(&patched)?;
+    if let Some(path) = unsupported(&doc, &round_trip, "") {
+        return Err(FafError::InvalidPatch(format!("unknown field {}", path)));
+    }
+    *data = patched;
+    Ok(())
+}
+
+/// Path tokens from a JSON Pointer or dotted path
+pub(crate) fn tokens(path: &str) -> Vec<String> {
+    let raw: Vec<String> = match path.strip_prefix('/') {
+        Some(pointer) => pointer
+            .split('/')
+            .map(|t| t.replace("~1", "/").replace("~0", "~"))
+            .collect(),
+        None if path.is_empty() => Vec::new(),
+        None => path.split('.').map(str::to_string).collect(),
+    };
+    raw.into_iter().map(|t| alias(&t).to_string()).collect()
+}
+
+fn alias(token: &str) -> &str {
+    match token {
+        "why_field" => "why",
+        "where_field" => "where",
+        other => other,
+    }
+}
+
+fn get<'a>(doc: &'a Value, tokens: &[String]) -> Option<&'a Value> {
+    tokens.iter().try_fold(doc, |node, token| match node {
+        Value::Object(map) => map.get(token),
+        Value::Array(items) => items.get(token.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+/// Parent container of `parent`, creating missing sections and lists
+///
+/// RFC 6902 requires the parent to exist; FAF sections are optional and
+/// empty lists are not serialized, so `add` creates them (`stack.database`
+/// on a file without `stack`, `tags.-` on a file without tags).
+fn parent_mut<'a>(
+    doc: &'a mut Value,
+    parent: &[String],
+    last: &str,
+    create: bool,
+) -> Result<&'a mut Value, String> {
+    let mut node = doc;
+    for (i, token) in parent.iter().enumerate() {
+        let next = parent.get(i + 1).map_or(last, String::as_str);
+        if let (true, Value::Object(map)) = (create, &mut *node) {
+            map.entry(token.clone()).or_insert_with(|| container(next));
+        }
+        node = match node {
+            Value::Object(map) => map
+                .get_mut(token)
+                .ok_or_else(|| format!("no such field {}", token))?,
+            Value::Array(items) => {
+                let index = token
+                    .parse::<usize>()
+                    .map_err(|_| format!("bad index {}", token))?;
+                items
+                    .get_mut(index)
+                    .ok_or_else(|| format!("index {} out of range", index))?
+            }
+            _ => return Err(format!("{} is not a section or list", token)),
+        };
+        if node.is_null() && create {
+            *node = container(next);
+        }
+    }
+    Ok(node)
+}
+
+/// Empty list if `next` is an index, otherwise an empty section
+fn container(next: &str) -> Value {
+    if next == "-" || next.parse::<usize>().is_ok() {
+        Value::Array(Vec::new())
+    } else {
+        Value::Object(Map::new())
+    }
+}
+
+fn split(path: &str) -> Result<(Vec<String>, String), String> {
+    let mut tokens = tokens(path);
+    let last = tokens.pop().ok_or("cannot patch the document root")?;
+    Ok((tokens, last))
+}
+
+fn add(doc: &mut Value, path: &str, value: Value) -> Result<(), String> {
+    let (parent, last) = split(path)?;
+    match parent_mut(doc, &parent, &last, true)? {
+        Value::Object(map) => {
+            map.insert(last, value);
+        }
+        Value::Array(items) if last == "-" => items.push(value),
+        Value::Array(items) => {
+            let index = last
+                .parse::<usize>()
+                .map_err(|_| format!("bad index {}", last))?;
+            if index > items.len() {
+                return Err(format!("index {} out of range", index));
+            }
+            items.insert(index, value);
+        }
+        _ => return Err(format!("{} is not a section or list", path)),
+    }
+    Ok(())
+}
+
+fn remove(doc: &mut Value, path: &str) -> Result<Value, String> {
+    let (parent, last) = split(path)?;
+    let missing = || format!("{} does not exist", path);
+    match parent_mut(doc, &parent, &last, false)? {
+        Value::Object(map) => map.remove(&last).ok_or_else(missing),
+        Value::Array(items) => match last.parse::<usize>() {
+            Ok(index) if index < items.len() => Ok(items.remove(index)),
+            _ => Err(missing()),
+        },
+        _ => Err(missing()),
+    }
+}
+
+fn apply_op(doc: &mut Value, op: &PatchOp) -> Result<(), String> {
+    match op {
+        PatchOp::Add { path, value } => add(doc, path, value.clone()),
+        PatchOp::Remove { path } => remove(doc, path).map(|_| ()),
+        PatchOp::Replace { path, value } => {
+            remove(doc, path)?;
+            add(doc, path, value.clone())
+        }
+        PatchOp::Move { from, path } => {
+            let value = remove(doc, from)?;
+            add(doc, path, value)
+        }
+        PatchOp::Copy { from, path } => {
+            let value = get(doc, &tokens(from))
+                .cloned()
+                .ok_or_else(|| format!("{} does not exist", from))?;
+            add(doc, path, value)
+        }
+        PatchOp::Test { path, value } => match get(doc, &tokens(path)) {
+            Some(actual) if actual == value => Ok(()),
+            _ => Err(format!("test failed at {}", path)),
+        },
+    }
+}
+
+/// Map `why_field`/`where_field` keys in a merge patch to the YAML names
+fn normalize_merge(patch: &Value) -> Value {
+    match patch {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (alias(k).to_string(), normalize_merge(v)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// RFC 7386 section 2
+fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(map) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                map.remove(key);
+            } else {
+                merge_patch(map.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// First field in the patched document that did not survive the schema
+fn unsupported(patched: &Value, typed: &Value, path: &str) -> Option<String> {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    match (patched, typed) {
+        (Value::Object(p), Value::Object(t)) => {
+            p.iter().find_map(|(key, value)| match t.get(key) {
+                Some(typed) => unsupported(value, typed, &child(key)),
+                None if is_empty(value) => None,
+                None => Some(child(key)),
+            })
+        }
+        (Value::Object(p), _) if p.values().all(is_empty) => None,
+        (p, t) if p == t || is_empty(p) => None,
+        _ => Some(path.to_string()),
+    }
+}
+
+/// Values serde skips on output (`None`, empty lists and maps)
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.values().all(is_empty),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn sample() -> FafData {
+        parse(
+            "faf_version: 2.5.0\nproject:\n  name: app\n  goal: Old\ninstant_context:\n  key_files: [a.rs, b.rs]\ntags: [rust]",
+        )
+        .unwrap()
+        .data
+    }
+
+    fn json(ops: &str) -> Patch {
+        Patch::from_json(ops).unwrap()
+    }
+
+    #[test]
+    fn test_json_patch_ops() {
+        let mut data = sample();
+        apply_patch(
+            &mut data,
+            &json(
+                r#"[
+                {"op": "test", "path": "/project/goal", "value": "Old"},
+                {"op": "replace", "path": "project.goal", "value": "New"},
+                {"op": "add", "path": "instant_context.key_files.0", "value": "main.rs"},
+                {"op": "remove", "path": "/instant_context/key_files/2"},
+                {"op": "copy", "from": "project.goal", "path": "human_context.where_field"},
+                {"op": "move", "from": "/tags/0", "path": "/tags/-"},
+                {"op": "add", "path": "stack.database", "value": "SQLite"},
+                {"op": "add", "path": "instant_context.commands.test", "value": "cargo test"},
+                {"op": "add", "path": "state.milestones.-", "value": "v1"}
+            ]"#,
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(data.project.goal.as_deref(), Some("New"));
+        let ic = data.instant_context.as_ref().unwrap();
+        assert_eq!(ic.key_files, vec!["main.rs", "a.rs"]);
+        assert_eq!(ic.commands["test"], "cargo test");
+        assert_eq!(
+            data.human_context.unwrap().where_field.as_deref(),
+            Some("New")
+        );
+        assert_eq!(data.stack.unwrap().database.as_deref(), Some("SQLite"));
+        assert_eq!(data.tags, vec!["rust"]);
+        assert_eq!(data.state.unwrap().milestones, vec!["v1"]);
+    }
+
+    #[test]
+    fn test_merge_patch() {
+        let mut data = sample();
+        apply_patch(
+            &mut data,
+            &json(r#"{"project": {"goal": null, "license": "MIT"}, "human_context": {"why_field": "Because"}, "tags": ["a", "b"]}"#),
+        )
+        .unwrap();
+        assert!(data.project.goal.is_none());
+        assert_eq!(data.project.license.as_deref(), Some("MIT"));
+        assert_eq!(
+            data.human_context.unwrap().why_field.as_deref(),
+            Some("Because")
+        );
+        assert_eq!(data.tags, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_bad_patches_are_atomic() {
+        let bad = [
+            // unknown field
+            r#"[{"op": "replace", "path": "project.goal", "value": "New"}, {"op": "add", "path": "project.colour", "value": "red"}]"#,
+            // wrong type
+            r#"[{"op": "replace", "path": "project.goal", "value": "New"}, {"op": "add", "path": "tags", "value": "rust"}]"#,
+            // failed test
+            r#"[{"op": "replace", "path": "project.goal", "value": "New"}, {"op": "test", "path": "project.name", "value": "other"}]"#,
+            // required field removed
+            r#"[{"op": "remove", "path": "/project/name"}]"#,
+            // missing target
+            r#"[{"op": "remove", "path": "stack.database"}]"#,
+            r#"{"project": {"goal": 1}}"#,
+            r#"{"sections": {"x": "y"}}"#,
+        ];
+        for patch in bad {
+            let mut data = sample();
+            let result = apply_patch(&mut data, &json(patch));
+            assert!(
+                matches!(result, Err(FafError::InvalidPatch(_))),
+                "{}",
+                patch
+            );
+            assert_eq!(data.project.goal.as_deref(), Some("Old"), "{}", patch);
+        }
+        assert!(Patch::from_json("42").is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! Generic field access by dotted path
+//!
+//! Paths use the YAML field names (`human_context.why`,
+//! `instant_context.commands.test`, `instant_context.key_files.0`).
+//! Patterns may use `*` to match any part of one segment
+//! (`instant_context.commands.*`, `*.testing`, `stack.*_tool`).
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::parser::{FafError, FafFile};
+use crate::patch::{apply_patch, tokens, Patch, PatchOp};
+use crate::types::FafData;
+
+/// Value of a field or section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    Text(String),
+    Bool(bool),
+    List(Vec<String>),
+    /// A section or map (`stack`, `instant_context.commands`)
+    Map(BTreeMap<String, FieldValue>),
+}
+
+impl FieldValue {
+    /// Text value, if this is text
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FieldValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// List items, if this is a list
+    pub fn as_list(&self) -> Option<&[String]> {
+        match self {
+            FieldValue::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Bool(b) => Some(FieldValue::Bool(*b)),
+            Value::Number(n) => Some(FieldValue::Text(n.to_string())),
+            Value::String(s) => Some(FieldValue::Text(s.clone())),
+            Value::Array(items) => Some(FieldValue::List(
+                items
+                    .iter()
+                    .filter_map(|v| match FieldValue::from_json(v)? {
+                        FieldValue::Text(text) => Some(text),
+                        other => Some(other.to_string()),
+                    })
+                    .collect(),
+            )),
+            Value::Object(map) => Some(FieldValue::Map(
+                map.iter()
+                    .filter_map(|(k, v)| Some((k.clone(), FieldValue::from_json(v)?)))
+                    .collect(),
+            )),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            FieldValue::Text(text) => Value::String(text.clone()),
+            FieldValue::Bool(b) => Value::Bool(*b),
+            FieldValue::List(items) => items.iter().cloned().map(Value::String).collect(),
+            FieldValue::Map(map) => {
+                Value::Object(map.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+            }
+        }
+    }
+}
+
+/// Text as-is, lists comma-separated, maps as `key=value` pairs
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Text(text) => write!(f, "{}", text),
+            FieldValue::Bool(b) => write!(f, "{}", b),
+            FieldValue::List(items) => write!(f, "{}", items.join(", ")),
+            FieldValue::Map(map) => {
+                let pairs: Vec<String> = map.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                write!(f, "{}", pairs.join(", "))
+            }
+        }
+    }
+}
+
+impl From<&str> for FieldValue {
+    fn from(text: &str) -> Self {
+        FieldValue::Text(text.to_string())
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(text: String) -> Self {
+        FieldValue::Text(text)
+    }
+}
+
+impl From<bool> for FieldValue {
+    fn from(b: bool) -> Self {
+        FieldValue::Bool(b)
+    }
+}
+
+impl From<Vec<String>> for FieldValue {
+    fn from(items: Vec<String>) -> Self {
+        FieldValue::List(items)
+    }
+}
+
+impl From<Vec<&str>> for FieldValue {
+    fn from(items: Vec<&str>) -> Self {
+        FieldValue::List(items.into_iter().map(str::to_string).collect())
+    }
+}
+
+impl FafData {
+    /// Value at a dotted path
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use faf_rust_sdk::{parse, FieldValue};
+    ///
+    /// let faf = parse("faf_version: 2.5.0\nproject:\n  name: app\nstack:\n  database: SQLite").unwrap();
+    /// assert_eq!(faf.get("stack.database"), Some(FieldValue::from("SQLite")));
+    /// assert_eq!(faf.get("stack.backend"), None);
+    /// ```
+    pub fn get(&self, path: &str) -> Option<FieldValue> {
+        let root = serde_json::to_value(self).ok()?;
+        let value = tokens(path)
+            .iter()
+            .try_fold(&root, |node, token| match node {
+                Value::Object(map) => map.get(token),
+                Value::Array(items) => items.get(token.parse::<usize>().ok()?),
+                _ => None,
+            })?;
+        FieldValue::from_json(value).filter(|v| !is_empty(v))
+    }
+
+    /// Set the value at a dotted path, creating missing sections
+    ///
+    /// The new value is type-checked; on error nothing changes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use faf_rust_sdk::parse;
+    ///
+    /// let mut faf = parse("faf_version: 2.5.0\nproject:\n  name: app").unwrap();
+    /// faf.set("instant_context.commands.test", "cargo test").unwrap();
+    /// faf.set("tags", vec!["rust", "cli"]).unwrap();
+    /// assert!(faf.set("tags", "not a list").is_err());
+    /// assert_eq!(faf.get("tags").unwrap().to_string(), "rust, cli");
+    /// ```
+    pub fn set<V: Into<FieldValue>>(&mut self, path: &str, value: V) -> Result<(), FafError> {
+        let op = PatchOp::Add {
+            path: path.to_string(),
+            value: value.into().to_json(),
+        };
+        apply_patch(self, &Patch::Json(vec![op]))
+    }
+
+    /// All populated fields as `(path, value)` pairs
+    ///
+    /// Maps are flattened (`instant_context.commands.build`); lists are
+    /// returned whole.
+    pub fn fields(&self) -> impl Iterator<Item = (String, FieldValue)> {
+        let mut out = Vec::new();
+        if let Ok(root) = serde_json::to_value(self) {
+            collect(&root, "", &mut out, &|_| true, true);
+        }
+        out.into_iter()
+    }
+
+    /// Fields and sections matching a `*` pattern
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use faf_rust_sdk::parse;
+    ///
+    /// let faf = parse(
+    ///     "faf_version: 2.5.0\nproject:\n  name: app\ninstant_context:\n  commands:\n    build: cargo build\n    test: cargo test",
+    /// ).unwrap();
+    /// let commands: Vec<_> = faf.query("instant_context.commands.*").map(|(path, _)| path).collect();
+    /// assert_eq!(commands, ["instant_context.commands.build", "instant_context.commands.test"]);
+    /// ```
+    pub fn query(&self, pattern: &str) -> impl Iterator<Item = (String, FieldValue)> {
+        let pattern = tokens(pattern);
+        let mut out = Vec::new();
+        if let Ok(root) = serde_json::to_value(self) {
+            let matches = |path: &str| {
+                let segments: Vec<&str> = path.split('.').collect();
+                segments.len() == pattern.len()
+                    && segments.iter().zip(&pattern).all(|(s, p)| wildcard(p, s))
+            };
+            collect(&root, "", &mut out, &matches, false);
+        }
+        out.into_iter()
+    }
+}
+
+impl FafFile {
+    /// Value at a dotted path (see [`FafData::get`])
+    pub fn get(&self, path: &str) -> Option<FieldValue> {
+        self.data.get(path)
+    }
+
+    /// Set the value at a dotted path (see [`FafData::set`])
+    pub fn set<V: Into<FieldValue>>(&mut self, path: &str, value: V) -> Result<(), FafError> {
+        self.data.set(path, value)
+    }
+
+    /// All populated fields (see [`FafData::fields`])
+    pub fn fields(&self) -> impl Iterator<Item = (String, FieldValue)> {
+        self.data.fields()
+    }
+
+    /// Fields and sections matching a `*` pattern (see [`FafData::query`])
+    pub fn query(&self, pattern: &str) -> impl Iterator<Item = (String, FieldValue)> {
+        self.data.query(pattern)
+    }
+}
+
+fn is_empty(value: &FieldValue) -> bool {
+    match value {
+        FieldValue::Text(text) => text.is_empty(),
+        FieldValue::Bool(_) => false,
+        FieldValue::List(items) => items.is_empty(),
+        FieldValue::Map(map) => map.values().all(is_empty),
+    }
+}
+
+/// Walk the tree, keeping nodes whose path matches; `leaves_only` skips
+/// sections and maps
+fn collect(
+    node: &Value,
+    path: &str,
+    out: &mut Vec<(String, FieldValue)>,
+    matches: &dyn Fn(&str) -> bool,
+    leaves_only: bool,
+) {
+    let skip = path.is_empty() || (leaves_only && node.is_object());
+    if !skip && matches(path) {
+        if let Some(value) = FieldValue::from_json(node).filter(|v| !is_empty(v)) {
+            out.push((path.to_string(), value));
+        }
+    }
+    if let Value::Object(map) = node {
+        for (key, child) in map {
+            let child_path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+            collect(child, &child_path, out, matches, leaves_only);
+        }
+    }
+}
+
+/// Match one segment against a pattern where `*` is any run of characters
+fn wildcard(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(tail) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=tail.len())
+                .filter(|&i| tail.is_char_boundary(i))
+                .any(|i| wildcard(rest, &tail[i..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_get_and_fields() {
+        let faf = parse(include_str!("../examples/full.faf")).unwrap();
+        assert_eq!(
+            faf.get("human_context.why").unwrap().as_str(),
+            Some("Demonstrate all FAF capabilities")
+        );
+        assert_eq!(
+            faf.get("human_context.why_field"),
+            faf.get("human_context.why")
+        );
+        assert_eq!(
+            faf.get("instant_context.key_files.1"),
+            Some(FieldValue::from("src/app.tsx"))
+        );
+        assert_eq!(
+            faf.get("instant_context.key_files")
+                .unwrap()
+                .as_list()
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(
+            faf.get("context_quality.handoff_ready"),
+            Some(FieldValue::Bool(true))
+        );
+        assert!(matches!(faf.get("stack"), Some(FieldValue::Map(m)) if m.len() == 7));
+        assert_eq!(faf.get("project.approach"), None);
+        assert_eq!(faf.get("nope.nothing"), None);
+
+        let fields: BTreeMap<_, _> = faf.fields().collect();
+        assert_eq!(
+            fields["instant_context.commands.dev"],
+            FieldValue::from("npm run dev")
+        );
+        assert_eq!(fields["tags"].as_list().unwrap().len(), 4);
+        assert!(!fields.contains_key("stack"));
+        assert!(!fields.keys().any(|k| k.starts_with("project.approach")));
+        // Every field reads back through get
+        for (path, value) in faf.fields() {
+            assert_eq!(faf.get(&path), Some(value), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_query_patterns() {
+        let faf = parse(include_str!("../examples/full.faf")).unwrap();
+        let paths = |pattern: &str| faf.query(pattern).map(|(p, _)| p).collect::<Vec<_>>();
+
+        assert_eq!(paths("instant_context.commands.*").len(), 4);
+        assert_eq!(paths("*.testing"), ["preferences.testing", "stack.testing"]);
+        assert_eq!(paths("stack.*_tool"), ["stack.build_tool"]);
+        assert!(paths("*").contains(&"human_context".to_string()));
+        assert!(paths("nothing.*").is_empty());
+    }
+
+    #[test]
+    fn test_set_is_checked() {
+        let mut faf = parse("faf_version: 2.5.0\nproject:\n  name: app").unwrap();
+        faf.set("human_context.where", "Remote").unwrap();
+        faf.set("context_quality.handoff_ready", true).unwrap();
+        assert_eq!(
+            faf.data
+                .human_context
+                .as_ref()
+                .unwrap()
+                .where_field
+                .as_deref(),
+            Some("Remote")
+        );
+        assert!(faf.data.context_quality.as_ref().unwrap().handoff_ready);
+
+        assert!(faf.set("project.unknown", "x").is_err());
+        assert!(faf.set("project.goal", true).is_err());
+        assert_eq!(faf.get("project.goal"), None);
+    }
+}
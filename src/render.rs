@@ -0,0 +1,576 @@
+//! Render FAF data as prompt text for LLMs
+//!
+//! Every format walks the same template, so the three outputs carry the
+//! same content in the same order:
+//!
+//! 1. `project`, `ai_tldr`, `instant_context`, `stack`, `human_context`,
+//!    `preferences`, `state`, `tags`
+//! 2. with [`RenderOptions::include_metadata`]: `faf` (`faf_version`,
+//!    `ai_score`, `ai_confidence`) and `context_quality`
+//!
+//! Fields keep their declaration order in [`FafData`]; map entries
+//! (`commands`, `ai_tldr`) are sorted by key. Empty fields and sections
+//! are left out.
+
+use crate::types::FafData;
+
+/// Output format for [`render`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `## Section` headings with bulleted fields
+    Markdown,
+    /// One `<section>` tag per section with a tag per field - suits system prompts
+    XmlTags,
+    /// Upper-case section titles with `Label: value` lines
+    PlainText,
+}
+
+/// Options for [`render`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Minimise tokens: no blank lines, indentation or emphasis, lists
+    /// inlined, whitespace inside values collapsed
+    pub compact: bool,
+    /// Also render version, scores and `context_quality`
+    pub include_metadata: bool,
+}
+
+impl RenderOptions {
+    /// Default options with compact mode on
+    pub fn compact() -> Self {
+        RenderOptions {
+            compact: true,
+            ..Default::default()
+        }
+    }
+}
+
+enum Value {
+    Text(String),
+    List(Vec<String>),
+    Pairs(Vec<(String, String)>),
+}
+
+struct Field {
+    key: &'static str,
+    name: String,
+    value: Value,
+}
+
+struct Section {
+    key: &'static str,
+    title: &'static str,
+    fields: Vec<Field>,
+}
+
+impl Section {
+    fn new(key: &'static str, title: &'static str) -> Self {
+        Section {
+            key,
+            title,
+            fields: Vec::new(),
+        }
+    }
+
+    fn text(mut self, key: &'static str, value: Option<&String>) -> Self {
+        if let Some(value) = value.filter(|v| !v.trim().is_empty()) {
+            self.fields.push(Field {
+                key,
+                name: key.to_string(),
+                value: Value::Text(value.clone()),
+            });
+        }
+        self
+    }
+
+    fn list(mut self, key: &'static str, items: &[String]) -> Self {
+        if !items.is_empty() {
+            self.fields.push(Field {
+                key,
+                name: key.to_string(),
+                value: Value::List(items.to_vec()),
+            });
+        }
+        self
+    }
+
+    fn pairs<'a>(
+        mut self,
+        key: &'static str,
+        map: impl IntoIterator<Item = (&'a String, &'a String)>,
+    ) -> Self {
+        let mut pairs: Vec<_> = map
+            .into_iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        if !pairs.is_empty() {
+            pairs.sort();
+            self.fields.push(Field {
+                key,
+                name: key.to_string(),
+                value: Value::Pairs(pairs),
+            });
+        }
+        self
+    }
+
+    /// A section holding a single list or map renders it without a label
+    fn bare(&self) -> Option<&Value> {
+        match self.fields.as_slice() {
+            [field] if field.key == self.key => Some(&field.value),
+            _ => None,
+        }
+    }
+}
+
+fn sections(data: &FafData, options: &RenderOptions) -> Vec<Section> {
+    let p = &data.project;
+    let mut out = vec![Section::new("project", "Project")
+        .text("name", Some(&p.name))
+        .text("goal", p.goal.as_ref())
+        .text("main_language", p.main_language.as_ref())
+        .text("approach", p.approach.as_ref())
+        .text("version", p.version.as_ref())
+        .text("license", p.license.as_ref())];
+
+    if let Some(tldr) = &data.ai_tldr {
+        let mut section = Section::new("ai_tldr", "TL;DR");
+        let mut entries: Vec<_> = tldr.iter().filter(|(_, v)| !v.trim().is_empty()).collect();
+        entries.sort();
+        for (key, value) in entries {
+            section.fields.push(Field {
+                key: "",
+                name: key.clone(),
+                value: Value::Text(value.clone()),
+            });
+        }
+        out.push(section);
+    }
+
+    if let Some(ic) = &data.instant_context {
+        out.push(
+            Section::new("instant_context", "Instant context")
+                .text("what_building", ic.what_building.as_ref())
+                .text("tech_stack", ic.tech_stack.as_ref())
+                .text("deployment", ic.deployment.as_ref())
+                .list("key_files", &ic.key_files)
+                .pairs("commands", &ic.commands),
+        );
+    }
+
+    if let Some(s) = &data.stack {
+        out.push(
+            Section::new("stack", "Stack")
+                .text("frontend", s.frontend.as_ref())
+                .text("backend", s.backend.as_ref())
+                .text("database", s.database.as_ref())
+                .text("infrastructure", s.infrastructure.as_ref())
+                .text("build_tool", s.build_tool.as_ref())
+                .text("testing", s.testing.as_ref())
+                .text("cicd", s.cicd.as_ref()),
+        );
+    }
+
+    if let Some(h) = &data.human_context {
+        out.push(
+            Section::new("human_context", "Human context")
+                .text("who", h.who.as_ref())
+                .text("what", h.what.as_ref())
+                .text("why", h.why_field.as_ref())
+                .text("how", h.how.as_ref())
+                .text("where", h.where_field.as_ref())
+                .text("when", h.when.as_ref()),
+        );
+    }
+
+    if let Some(pr) = &data.preferences {
+        out.push(
+            Section::new("preferences", "Preferences")
+                .text("quality_bar", pr.quality_bar.as_ref())
+                .text("testing", pr.testing.as_ref())
+                .text("documentation", pr.documentation.as_ref())
+                .text("code_style", pr.code_style.as_ref()),
+        );
+    }
+
+    if let Some(st) = &data.state {
+        out.push(
+            Section::new("state", "State")
+                .text("phase", st.phase.as_ref())
+                .text("version", st.version.as_ref())
+                .text("focus", st.focus.as_ref())
+                .list("milestones", &st.milestones),
+        );
+    }
+
+    out.push(Section::new("tags", "Tags").list("tags", &data.tags));
+
+    if options.include_metadata {
+        out.push(
+            Section::new("faf", "FAF")
+                .text("faf_version", Some(&data.faf_version))
+                .text("ai_score", data.ai_score.as_ref())
+                .text("ai_confidence", data.ai_confidence.as_ref()),
+        );
+        if let Some(cq) = &data.context_quality {
+            let handoff = cq.handoff_ready.to_string();
+            out.push(
+                Section::new("context_quality", "Context quality")
+                    .text("slots_filled", cq.slots_filled.as_ref())
+                    .text("confidence", cq.confidence.as_ref())
+                    .text("handoff_ready", Some(&handoff))
+                    .list("missing_context", &cq.missing_context),
+            );
+        }
+    }
+
+    out.retain(|s| !s.fields.is_empty());
+    out
+}
+
+/// Human label for a field name (`main_language` -> `Main language`)
+fn label(name: &str) -> String {
+    match name {
+        "faf_version" => return "FAF version".to_string(),
+        "ai_score" => return "AI score".to_string(),
+        "ai_confidence" => return "AI confidence".to_string(),
+        "cicd" => return "CI/CD".to_string(),
+        _ => {}
+    }
+    let spaced = name.replace('_', " ");
+    let mut chars = spaced.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => spaced,
+    }
+}
+
+fn squash(text: &str, compact: bool) -> String {
+    if compact {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    } else {
+        text.trim_end().to_string()
+    }
+}
+
+/// Markdown code span, fenced with more backticks than any run in `text`
+fn code(text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest + 1);
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{} {} {}", fence, text, fence)
+    } else {
+        format!("{}{}{}", fence, text, fence)
+    }
+}
+
+fn inline(value: &Value, compact: bool) -> String {
+    match value {
+        Value::Text(text) => squash(text, compact),
+        Value::List(items) => items
+            .iter()
+            .map(|i| squash(i, compact))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Pairs(pairs) => pairs
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, squash(v, compact)))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+/// Render FAF data as prompt text
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{parse, render, Format, RenderOptions};
+///
+/// let faf = parse("faf_version: 2.5.0\nproject:\n  name: test\n  goal: Testing").unwrap();
+///
+/// let md = render(&faf.data, Format::Markdown, &RenderOptions::default());
+/// assert_eq!(md, "## Project\n\n- **Name:** test\n- **Goal:** Testing\n");
+///
+/// let xml = render(&faf.data, Format::XmlTags, &RenderOptions::compact());
+/// assert_eq!(xml, "<project>\n<name>test</name>\n<goal>Testing</goal>\n</project>\n");
+/// ```
+pub fn render(data: &FafData, format: Format, options: &RenderOptions) -> String {
+    let sections = sections(data, options);
+    let blocks: Vec<String> = sections
+        .iter()
+        .map(|section| match format {
+            Format::Markdown => markdown(section, options.compact),
+            Format::XmlTags => xml(section, options.compact),
+            Format::PlainText => plain(section, options.compact),
+        })
+        .collect();
+
+    let separator = if options.compact || format == Format::XmlTags {
+        ""
+    } else {
+        "\n"
+    };
+    blocks.join(separator)
+}
+
+fn markdown(section: &Section, compact: bool) -> String {
+    let mut out = format!("## {}\n", section.title);
+    if !compact {
+        out.push('\n');
+    }
+
+    if let Some(value) = section.bare() {
+        match value {
+            Value::List(items) if !compact => {
+                for item in items {
+                    out.push_str(&format!("- {}\n", squash(item, compact)));
+                }
+            }
+            _ => out.push_str(&format!("{}\n", inline(value, compact))),
+        }
+        return out;
+    }
+
+    for field in &section.fields {
+        let name = label(&field.name);
+        match (&field.value, compact) {
+            (value, true) => out.push_str(&format!("- {}: {}\n", name, inline(value, true))),
+            (Value::Text(text), false) => {
+                out.push_str(&format!("- **{}:** {}\n", name, squash(text, false)))
+            }
+            (Value::List(items), false) => {
+                out.push_str(&format!("- **{}:**\n", name));
+                for item in items {
+                    out.push_str(&format!("  - {}\n", squash(item, false)));
+                }
+            }
+            (Value::Pairs(pairs), false) => {
+                out.push_str(&format!("- **{}:**\n", name));
+                for (k, v) in pairs {
+                    out.push_str(&format!("  - {}: {}\n", code(k), code(&squash(v, false))));
+                }
+            }
+        }
+    }
+    out
+}
+
+fn plain(section: &Section, compact: bool) -> String {
+    let mut out = format!("{}\n", section.title.to_uppercase());
+
+    if let Some(value) = section.bare() {
+        match value {
+            Value::List(items) if !compact => {
+                for item in items {
+                    out.push_str(&format!("- {}\n", squash(item, compact)));
+                }
+            }
+            _ => out.push_str(&format!("{}\n", inline(value, compact))),
+        }
+        return out;
+    }
+
+    for field in &section.fields {
+        let name = label(&field.name);
+        match (&field.value, compact) {
+            (Value::Text(_), _) | (_, true) => {
+                out.push_str(&format!("{}: {}\n", name, inline(&field.value, compact)))
+            }
+            (Value::List(items), false) => {
+                out.push_str(&format!("{}:\n", name));
+                for item in items {
+                    out.push_str(&format!("- {}\n", squash(item, false)));
+                }
+            }
+            (Value::Pairs(pairs), false) => {
+                out.push_str(&format!("{}:\n", name));
+                for (k, v) in pairs {
+                    out.push_str(&format!("- {}: {}\n", k, squash(v, false)));
+                }
+            }
+        }
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Tag name for a field, falling back to `<entry key="...">` for map keys
+/// that are not valid XML names
+fn xml_tag(name: &str) -> Option<&str> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    valid.then_some(name)
+}
+
+fn xml(section: &Section, compact: bool) -> String {
+    let indent = if compact { "" } else { "  " };
+    let mut out = format!("<{}>\n", section.key);
+
+    let items = |out: &mut String, value: &Value, depth: &str| match value {
+        Value::Text(text) => {
+            out.push_str(&format!("{}{}\n", depth, escape(&squash(text, compact))))
+        }
+        Value::List(items) => {
+            for item in items {
+                out.push_str(&format!(
+                    "{}<item>{}</item>\n",
+                    depth,
+                    escape(&squash(item, compact))
+                ));
+            }
+        }
+        Value::Pairs(pairs) => {
+            for (k, v) in pairs {
+                out.push_str(&format!(
+                    "{}<item key=\"{}\">{}</item>\n",
+                    depth,
+                    escape(k),
+                    escape(&squash(v, compact))
+                ));
+            }
+        }
+    };
+
+    if let Some(value) = section.bare() {
+        match compact {
+            true => out.push_str(&format!("{}\n", escape(&inline(value, true)))),
+            false => items(&mut out, value, indent),
+        }
+    } else {
+        for field in &section.fields {
+            let (open, close) = match xml_tag(&field.name) {
+                Some(tag) => (tag.to_string(), tag.to_string()),
+                None => (
+                    format!("entry key=\"{}\"", escape(&field.name)),
+                    "entry".to_string(),
+                ),
+            };
+            match &field.value {
+                value if compact || matches!(value, Value::Text(_)) => out.push_str(&format!(
+                    "{}<{}>{}</{}>\n",
+                    indent,
+                    open,
+                    escape(&inline(value, compact)),
+                    close
+                )),
+                value => {
+                    out.push_str(&format!("{}<{}>\n", indent, open));
+                    items(&mut out, value, &indent.repeat(2));
+                    out.push_str(&format!("{}</{}>\n", indent, close));
+                }
+            }
+        }
+    }
+
+    out.push_str(&format!("</{}>\n", section.key));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, stringify};
+
+    const SAMPLE: &str = r#"
+faf_version: 2.5.0
+ai_score: "40%"
+project:
+  name: demo
+  goal: Ship <fast> & safe
+instant_context:
+  key_files: [src/lib.rs, Cargo.toml]
+  commands:
+    test: cargo test
+    build: cargo build
+stack:
+  cicd: GitHub Actions
+tags: [rust, cli]
+"#;
+
+    #[test]
+    fn test_markdown_template() {
+        let faf = parse(SAMPLE).unwrap();
+        let md = render(&faf.data, Format::Markdown, &RenderOptions::default());
+        assert_eq!(
+            md,
+            "## Project\n\n- **Name:** demo\n- **Goal:** Ship <fast> & safe\n\n\
+             ## Instant context\n\n- **Key files:**\n  - src/lib.rs\n  - Cargo.toml\n\
+             - **Commands:**\n  - `build`: `cargo build`\n  - `test`: `cargo test`\n\n\
+             ## Stack\n\n- **CI/CD:** GitHub Actions\n\n\
+             ## Tags\n\n- rust\n- cli\n"
+        );
+        assert!(!md.contains("AI score"));
+    }
+
+    #[test]
+    fn test_markdown_code_spans_hold_backticks() {
+        let faf = parse(
+            "faf_version: 2.5.0\nproject:\n  name: demo\ninstant_context:\n  commands:\n    \
+             version: echo `git describe`\n    quote: \"``x``\"\n",
+        )
+        .unwrap();
+        let md = render(&faf.data, Format::Markdown, &RenderOptions::default());
+        assert!(md.contains("  - `quote`: ``` ``x`` ```\n"));
+        assert!(md.contains("  - `version`: `` echo `git describe` ``\n"));
+    }
+
+    #[test]
+    fn test_xml_escapes_and_metadata() {
+        let faf = parse(SAMPLE).unwrap();
+        let options = RenderOptions {
+            include_metadata: true,
+            ..Default::default()
+        };
+        let xml = render(&faf.data, Format::XmlTags, &options);
+        assert!(xml.contains("  <goal>Ship &lt;fast&gt; &amp; safe</goal>\n"));
+        assert!(xml.contains(
+            "  <commands>\n    <item key=\"build\">cargo build</item>\n    <item key=\"test\">cargo test</item>\n  </commands>\n"
+        ));
+        assert!(xml.contains("<tags>\n  <item>rust</item>\n  <item>cli</item>\n</tags>\n"));
+
+        let compact = render(&faf.data, Format::XmlTags, &RenderOptions::compact());
+        assert!(compact.contains("<key_files>src/lib.rs, Cargo.toml</key_files>\n"));
+        assert!(compact.ends_with("<tags>\nrust, cli\n</tags>\n"));
+        assert!(xml.ends_with(
+            "<faf>\n  <faf_version>2.5.0</faf_version>\n  <ai_score>40%</ai_score>\n</faf>\n"
+        ));
+    }
+
+    #[test]
+    fn test_plain_and_compact() {
+        let faf = parse(SAMPLE).unwrap();
+        let plain = render(&faf.data, Format::PlainText, &RenderOptions::compact());
+        assert_eq!(
+            plain,
+            "PROJECT\nName: demo\nGoal: Ship <fast> & safe\n\
+             INSTANT CONTEXT\nKey files: src/lib.rs, Cargo.toml\nCommands: build=cargo build, test=cargo test\n\
+             STACK\nCI/CD: GitHub Actions\n\
+             TAGS\nrust, cli\n"
+        );
+
+        // Compact output is smaller than the full render, and Markdown or
+        // plain text beat the YAML itself
+        let full = parse(include_str!("../examples/full.faf")).unwrap();
+        let yaml = stringify(&full).unwrap();
+        for format in [Format::Markdown, Format::XmlTags, Format::PlainText] {
+            let compact = render(&full.data, format, &RenderOptions::compact());
+            let verbose = render(&full.data, format, &RenderOptions::default());
+            assert!(compact.len() < verbose.len());
+            assert!(format == Format::XmlTags || compact.len() < yaml.len());
+        }
+    }
+}
@@ -0,0 +1,568 @@
+//! Built-in validation rules
+
+use std::path::{Path, PathBuf};
+
+use crate::parser::FafFile;
+use crate::scoring::{slots_filled, ScoringModel, SLOTS};
+use crate::types::FafData;
+use crate::validator::{Finding, Rule, RuleContext, Severity};
+
+/// Allowed gap between declared `ai_score` and the computed score
+pub(crate) const SCORE_TOLERANCE: u8 = 10;
+
+/// Minimum computed score for `handoff_ready: true` (matches `is_high_quality`)
+pub(crate) const HANDOFF_MIN_SCORE: u8 = 70;
+
+/// Build tools and the manifests they need (any one of them)
+const TOOL_MANIFESTS: &[(&str, &[&str])] = &[
+    ("npm", &["package.json"]),
+    ("npx", &["package.json"]),
+    ("yarn", &["package.json"]),
+    ("pnpm", &["package.json"]),
+    ("bun", &["package.json"]),
+    ("cargo", &["Cargo.toml"]),
+    ("go", &["go.mod"]),
+    ("make", &["Makefile", "makefile", "GNUmakefile"]),
+    ("poetry", &["pyproject.toml"]),
+    ("uv", &["pyproject.toml"]),
+    ("mvn", &["pom.xml"]),
+    ("gradle", &["build.gradle", "build.gradle.kts"]),
+    ("./gradlew", &["build.gradle", "build.gradle.kts"]),
+    ("bundle", &["Gemfile"]),
+    ("rake", &["Rakefile"]),
+    ("composer", &["composer.json"]),
+    ("dotnet", &["*.csproj", "*.sln"]),
+    (
+        "docker-compose",
+        &[
+            "docker-compose.yml",
+            "docker-compose.yaml",
+            "compose.yml",
+            "compose.yaml",
+        ],
+    ),
+];
+
+/// All built-in rules, in id order
+pub(crate) fn builtin_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(PresenceRule {
+            id: "FAF001",
+            name: "missing-project-name",
+            severity: Severity::Error,
+            message: "Missing project.name",
+            fix: "Set project.name to the repository or package name",
+            present: |d| !d.project.name.is_empty(),
+        }),
+        Box::new(PresenceRule {
+            id: "FAF002",
+            name: "missing-faf-version",
+            severity: Severity::Error,
+            message: "Missing faf_version",
+            fix: "Add faf_version: 2.5.0",
+            present: |d| !d.faf_version.is_empty(),
+        }),
+        Box::new(PresenceRule {
+            id: "FAF003",
+            name: "missing-instant-context",
+            severity: Severity::Warning,
+            message: "Missing instant_context section",
+            fix: "Add an instant_context section with what_building and tech_stack",
+            present: |d| d.instant_context.is_some(),
+        }),
+        Box::new(PresenceRule {
+            id: "FAF004",
+            name: "missing-what-building",
+            severity: Severity::Warning,
+            message: "Missing instant_context.what_building",
+            fix: "Describe what is being built in one line",
+            present: |d| {
+                d.instant_context
+                    .as_ref()
+                    .is_none_or(|ic| ic.what_building.is_some())
+            },
+        }),
+        Box::new(PresenceRule {
+            id: "FAF005",
+            name: "missing-tech-stack",
+            severity: Severity::Warning,
+            message: "Missing instant_context.tech_stack",
+            fix: "List the main languages and frameworks",
+            present: |d| {
+                d.instant_context
+                    .as_ref()
+                    .is_none_or(|ic| ic.tech_stack.is_some())
+            },
+        }),
+        Box::new(PresenceRule {
+            id: "FAF006",
+            name: "missing-stack",
+            severity: Severity::Warning,
+            message: "Missing stack section",
+            fix: "Add a stack section (backend, build_tool, testing, ...)",
+            present: |d| d.stack.is_some(),
+        }),
+        Box::new(PresenceRule {
+            id: "FAF007",
+            name: "missing-human-context",
+            severity: Severity::Warning,
+            message: "Missing human_context section",
+            fix: "Add a human_context section (who, what, why)",
+            present: |d| d.human_context.is_some(),
+        }),
+        Box::new(ScoreFormat),
+        Box::new(DuplicateEntries {
+            id: "FAF009",
+            name: "duplicate-tags",
+            path: "tags",
+            list: |d| Some(&d.tags),
+        }),
+        Box::new(DuplicateEntries {
+            id: "FAF010",
+            name: "duplicate-key-files",
+            path: "instant_context.key_files",
+            list: |d| d.instant_context.as_ref().map(|ic| &ic.key_files),
+        }),
+        Box::new(DeclaredScore),
+        Box::new(DeclaredSlots),
+        Box::new(HandoffReady),
+        Box::new(MissingContextFilled),
+        Box::new(KeyFileExists),
+        Box::new(KeyFileGlobMatches),
+        Box::new(CommandManifest),
+    ]
+}
+
+/// Flags a field or section that should be present
+struct PresenceRule {
+    id: &'static str,
+    name: &'static str,
+    severity: Severity,
+    message: &'static str,
+    fix: &'static str,
+    present: fn(&FafData) -> bool,
+}
+
+impl Rule for PresenceRule {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check(&self, faf: &FafFile, _ctx: &RuleContext) -> Vec<Finding> {
+        if (self.present)(&faf.data) {
+            Vec::new()
+        } else {
+            vec![Finding::new(self.message).with_fix(self.fix)]
+        }
+    }
+}
+
+/// FAF008: ai_score should be written as `NN%`
+struct ScoreFormat;
+
+impl Rule for ScoreFormat {
+    fn id(&self) -> &str {
+        "FAF008"
+    }
+
+    fn name(&self) -> &str {
+        "ai-score-format"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, faf: &FafFile, _ctx: &RuleContext) -> Vec<Finding> {
+        let Some(raw) = &faf.data.ai_score else {
+            return Vec::new();
+        };
+        match normalize_score(raw) {
+            Some(normalized) if &normalized == raw => Vec::new(),
+            Some(normalized) => {
+                vec![
+                    Finding::new(format!("ai_score '{}' is not in NN% format", raw))
+                        .with_fix(format!("Write ai_score: \"{}\"", normalized)),
+                ]
+            }
+            None => vec![Finding::new(format!(
+                "ai_score '{}' is not a percentage between 0% and 100%",
+                raw
+            ))],
+        }
+    }
+}
+
+/// Flags repeated entries in a string list
+struct DuplicateEntries {
+    id: &'static str,
+    name: &'static str,
+    path: &'static str,
+    list: fn(&FafData) -> Option<&Vec<String>>,
+}
+
+impl Rule for DuplicateEntries {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, faf: &FafFile, _ctx: &RuleContext) -> Vec<Finding> {
+        let Some(list) = (self.list)(&faf.data) else {
+            return Vec::new();
+        };
+        let mut seen = Vec::new();
+        let mut findings = Vec::new();
+        for item in list {
+            if seen.contains(&item) {
+                findings.push(
+                    Finding::new(format!("{} lists '{}' more than once", self.path, item))
+                        .with_fix(format!("Remove the duplicate '{}'", item)),
+                );
+            } else {
+                seen.push(item);
+            }
+        }
+        findings
+    }
+}
+
+/// FAF011: declared ai_score should be close to the computed score
+struct DeclaredScore;
+
+impl Rule for DeclaredScore {
+    fn id(&self) -> &str {
+        "FAF011"
+    }
+
+    fn name(&self) -> &str {
+        "ai-score-mismatch"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, faf: &FafFile, ctx: &RuleContext) -> Vec<Finding> {
+        let Some(declared) = faf.data.ai_score.as_deref().and_then(normalize_score) else {
+            return Vec::new();
+        };
+        let declared: u8 = declared.trim_end_matches('%').parse().unwrap_or(0);
+        let computed = ctx.score(faf);
+        if declared.abs_diff(computed) <= SCORE_TOLERANCE {
+            return Vec::new();
+        }
+        let kind = if declared > computed {
+            "inflated"
+        } else {
+            "stale"
+        };
+        vec![Finding::new(format!(
+            "ai_score {}% looks {}: computed score is {}%",
+            declared, kind, computed
+        ))
+        .with_fix(format!("Write ai_score: \"{}%\"", computed))]
+    }
+}
+
+/// FAF012: declared slots_filled should match the 24-slot count
+struct DeclaredSlots;
+
+impl Rule for DeclaredSlots {
+    fn id(&self) -> &str {
+        "FAF012"
+    }
+
+    fn name(&self) -> &str {
+        "slots-filled-mismatch"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, faf: &FafFile, _ctx: &RuleContext) -> Vec<Finding> {
+        let Some(declared) = faf
+            .data
+            .context_quality
+            .as_ref()
+            .and_then(|cq| cq.slots_filled.as_deref())
+        else {
+            return Vec::new();
+        };
+        let (filled, total) = slots_filled(&faf.data);
+        let actual = format!("{}/{}", filled, total);
+        if declared.replace(' ', "") == actual {
+            return Vec::new();
+        }
+        vec![Finding::new(format!(
+            "context_quality.slots_filled is {} but {} slots are filled",
+            declared, actual
+        ))
+        .with_fix(format!("Write slots_filled: \"{}\"", actual))]
+    }
+}
+
+/// FAF013: handoff_ready needs a high computed score
+struct HandoffReady;
+
+impl Rule for HandoffReady {
+    fn id(&self) -> &str {
+        "FAF013"
+    }
+
+    fn name(&self) -> &str {
+        "handoff-not-ready"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, faf: &FafFile, ctx: &RuleContext) -> Vec<Finding> {
+        let ready = faf
+            .data
+            .context_quality
+            .as_ref()
+            .is_some_and(|cq| cq.handoff_ready);
+        let computed = ctx.score(faf);
+        if !ready || computed >= HANDOFF_MIN_SCORE {
+            return Vec::new();
+        }
+        vec![Finding::new(format!(
+            "handoff_ready is true but computed score is {}% (needs {}%)",
+            computed, HANDOFF_MIN_SCORE
+        ))
+        .with_fix("Set handoff_ready: false or fill more slots")]
+    }
+}
+
+/// FAF014: missing_context should not list fields that are filled
+struct MissingContextFilled;
+
+impl Rule for MissingContextFilled {
+    fn id(&self) -> &str {
+        "FAF014"
+    }
+
+    fn name(&self) -> &str {
+        "missing-context-filled"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, faf: &FafFile, _ctx: &RuleContext) -> Vec<Finding> {
+        filled_missing_context(&faf.data)
+            .into_iter()
+            .map(|(entry, path)| {
+                Finding::new(format!(
+                    "missing_context lists '{}' but {} is filled",
+                    entry, path
+                ))
+                .with_fix(format!("Remove '{}' from missing_context", entry))
+            })
+            .collect()
+    }
+}
+
+/// `missing_context` entries naming a slot that is actually filled
+///
+/// Entries match a slot by full path (`stack.database`) or field name
+/// (`database`, `build tool`); free-form entries are ignored.
+pub(crate) fn filled_missing_context(data: &FafData) -> Vec<(String, &'static str)> {
+    let Some(cq) = &data.context_quality else {
+        return Vec::new();
+    };
+    let breakdown = ScoringModel::default().score(data);
+
+    cq.missing_context
+        .iter()
+        .filter_map(|entry| {
+            let key = entry.trim().to_lowercase().replace([' ', '-'], "_");
+            let path = SLOTS
+                .iter()
+                .find(|path| **path == key || path.rsplit('.').next() == Some(key.as_str()))?;
+            let filled = breakdown
+                .slots
+                .iter()
+                .any(|s| s.path == *path && s.is_complete());
+            filled.then(|| (entry.clone(), *path))
+        })
+        .collect()
+}
+
+/// Canonical `NN%` form of a score, or None if it is not a 0-100 percentage
+pub(crate) fn normalize_score(raw: &str) -> Option<String> {
+    let number = raw.trim().trim_end_matches('%').trim();
+    let value: f64 = number.parse().ok()?;
+    if (0.0..=100.0).contains(&value) {
+        Some(format!("{}%", value.round() as u8))
+    } else {
+        None
+    }
+}
+
+/// FAF101: literal key_files entries must exist under the root
+struct KeyFileExists;
+
+impl Rule for KeyFileExists {
+    fn id(&self) -> &str {
+        "FAF101"
+    }
+
+    fn name(&self) -> &str {
+        "key-file-missing"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, faf: &FafFile, ctx: &RuleContext) -> Vec<Finding> {
+        let Some(root) = ctx.root else {
+            return Vec::new();
+        };
+        faf.key_files()
+            .iter()
+            .filter(|file| !is_glob(file) && !root.join(file).exists())
+            .map(|file| {
+                Finding::new(format!("key_files entry '{}' does not exist", file))
+                    .with_fix(format!("Remove '{}' from instant_context.key_files", file))
+            })
+            .collect()
+    }
+}
+
+/// FAF102: glob key_files entries must match at least one file
+struct KeyFileGlobMatches;
+
+impl Rule for KeyFileGlobMatches {
+    fn id(&self) -> &str {
+        "FAF102"
+    }
+
+    fn name(&self) -> &str {
+        "key-file-glob-empty"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, faf: &FafFile, ctx: &RuleContext) -> Vec<Finding> {
+        let Some(root) = ctx.root else {
+            return Vec::new();
+        };
+        faf.key_files()
+            .iter()
+            .filter(|file| is_glob(file) && glob_matches(root, file) == 0)
+            .map(|file| Finding::new(format!("key_files pattern '{}' matches no files", file)))
+            .collect()
+    }
+}
+
+/// FAF103: commands should only invoke tools whose manifest exists
+struct CommandManifest;
+
+impl Rule for CommandManifest {
+    fn id(&self) -> &str {
+        "FAF103"
+    }
+
+    fn name(&self) -> &str {
+        "command-manifest-missing"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Hint
+    }
+
+    fn check(&self, faf: &FafFile, ctx: &RuleContext) -> Vec<Finding> {
+        let (Some(root), Some(ic)) = (ctx.root, &faf.data.instant_context) else {
+            return Vec::new();
+        };
+
+        let mut commands: Vec<_> = ic.commands.iter().collect();
+        commands.sort();
+
+        let mut findings = Vec::new();
+        for (name, command) in commands {
+            for (dir, tool) in command_tools(command) {
+                let Some((_, manifests)) = TOOL_MANIFESTS.iter().find(|(t, _)| *t == tool) else {
+                    continue;
+                };
+                // After `cd $VAR` or similar the directory is unknown
+                let Some(dir) = dir else {
+                    continue;
+                };
+                let dir = root.join(dir);
+                let found = manifests.iter().any(|m| {
+                    if is_glob(m) {
+                        glob_matches(&dir, m) > 0
+                    } else {
+                        dir.join(m).is_file()
+                    }
+                });
+                if !found {
+                    findings.push(Finding::new(format!(
+                        "commands.{} uses '{}' but no {} found",
+                        name,
+                        tool,
+                        manifests.join(" or ")
+                    )));
+                }
+            }
+        }
+        findings
+    }
+}
+
+fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+fn glob_matches(root: &Path, pattern: &str) -> usize {
+    let full = Path::new(&glob::Pattern::escape(&root.to_string_lossy())).join(pattern);
+    glob::glob(&full.to_string_lossy())
+        .map(|paths| paths.flatten().count())
+        .unwrap_or(0)
+}
+
+/// Program names invoked by a shell command line (`a && b | c; d`), each
+/// with the directory it runs in relative to the root, following `cd`
+/// (`None` once that directory is unknown)
+fn command_tools(command: &str) -> Vec<(Option<PathBuf>, &str)> {
+    let mut cwd = Some(PathBuf::new());
+    let mut tools = Vec::new();
+    for segment in command.split(['&', '|', ';']) {
+        let mut words = segment.split_whitespace().skip_while(|word| word.contains('='));
+        match words.next() {
+            Some("cd") => {
+                cwd = match words.next() {
+                    Some(dir) if !dir.starts_with(['$', '~', '-']) => cwd.map(|c| c.join(dir)),
+                    _ => None,
+                };
+            }
+            Some(tool) => tools.push((cwd.clone(), tool)),
+            None => {}
+        }
+    }
+    tools
+}
@@ -0,0 +1,336 @@
+//! Configurable scoring model for FAF completeness
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::parser::FafError;
+use crate::types::FafData;
+
+/// The 24 FAF context slots counted by `context_quality.slots_filled`
+pub const SLOTS: [&str; 24] = [
+    "project.name",
+    "project.goal",
+    "project.main_language",
+    "project.approach",
+    "project.version",
+    "project.license",
+    "instant_context.what_building",
+    "instant_context.tech_stack",
+    "instant_context.deployment",
+    "instant_context.key_files",
+    "instant_context.commands",
+    "stack.frontend",
+    "stack.backend",
+    "stack.database",
+    "stack.infrastructure",
+    "stack.build_tool",
+    "stack.testing",
+    "stack.cicd",
+    "human_context.who",
+    "human_context.what",
+    "human_context.why",
+    "human_context.how",
+    "human_context.where",
+    "human_context.when",
+];
+
+/// Weight of a single slot in a scoring model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlotWeight {
+    /// Dotted field path using YAML names (`human_context.why`, `ai_tldr`)
+    pub path: String,
+    /// Points this slot is worth relative to the others
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    /// For lists and maps: entries needed for full credit (fewer earn a share)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<usize>,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+/// Weighted slots used to compute a 0-100 score
+///
+/// The default model gives equal weight to the 24 FAF slots. Custom models
+/// load from YAML:
+///
+/// ```yaml
+/// slots:
+///   - path: project.goal
+///     weight: 3
+///   - path: instant_context.key_files
+///     weight: 2
+///     target: 5
+///   - path: preferences.testing
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoringModel {
+    pub slots: Vec<SlotWeight>,
+}
+
+impl Default for ScoringModel {
+    fn default() -> Self {
+        ScoringModel {
+            slots: SLOTS
+                .iter()
+                .map(|path| SlotWeight {
+                    path: path.to_string(),
+                    weight: 1.0,
+                    target: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Points earned by one slot
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotScore {
+    pub path: String,
+    /// Points available
+    pub weight: f64,
+    /// Points earned (between 0 and `weight`)
+    pub earned: f64,
+}
+
+impl SlotScore {
+    /// True if the slot earned full credit
+    pub fn is_complete(&self) -> bool {
+        self.earned >= self.weight
+    }
+}
+
+/// Per-slot result of scoring a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreBreakdown {
+    /// One entry per model slot, in model order
+    pub slots: Vec<SlotScore>,
+    /// Overall score (0-100)
+    pub score: u8,
+}
+
+impl ScoreBreakdown {
+    /// Total points earned
+    pub fn earned(&self) -> f64 {
+        self.slots.iter().map(|s| s.earned).sum()
+    }
+
+    /// Total points available
+    pub fn possible(&self) -> f64 {
+        self.slots.iter().map(|s| s.weight).sum()
+    }
+
+    /// Incomplete slots, most valuable first - what to fill next
+    pub fn next_to_fill(&self) -> Vec<&SlotScore> {
+        let mut missing: Vec<_> = self.slots.iter().filter(|s| !s.is_complete()).collect();
+        missing.sort_by(|a, b| {
+            (b.weight - b.earned)
+                .partial_cmp(&(a.weight - a.earned))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        missing
+    }
+}
+
+impl ScoringModel {
+    /// Parse a model from YAML
+    ///
+    /// Weights must be finite and not negative.
+    pub fn from_yaml(content: &str) -> Result<Self, FafError> {
+        let model: ScoringModel = serde_yaml::from_str(content)?;
+        if let Some(slot) = model
+            .slots
+            .iter()
+            .find(|s| !s.weight.is_finite() || s.weight < 0.0)
+        {
+            return Err(FafError::InvalidScoringModel(format!(
+                "{} has weight {}, expected a finite number >= 0",
+                slot.path, slot.weight
+            )));
+        }
+        Ok(model)
+    }
+
+    /// Load a model from a YAML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, FafError> {
+        Self::from_yaml(&fs::read_to_string(path)?)
+    }
+
+    /// Score FAF data against this model
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use faf_rust_sdk::{parse, ScoringModel};
+    ///
+    /// let faf = parse("faf_version: 2.5.0\nproject:\n  name: test\n  goal: Testing").unwrap();
+    /// let breakdown = ScoringModel::default().score(&faf.data);
+    /// assert_eq!(breakdown.score, 8); // 2 of 24 slots
+    /// println!("Fill next: {}", breakdown.next_to_fill()[0].path);
+    /// ```
+    pub fn score(&self, data: &FafData) -> ScoreBreakdown {
+        let root = serde_yaml::to_value(data).unwrap_or(Value::Null);
+        let slots: Vec<SlotScore> = self
+            .slots
+            .iter()
+            .map(|slot| {
+                let fill = lookup(&root, &slot.path).map_or(0.0, |v| fill_ratio(v, slot.target));
+                SlotScore {
+                    path: slot.path.clone(),
+                    weight: slot.weight,
+                    earned: slot.weight * fill,
+                }
+            })
+            .collect();
+
+        let possible: f64 = slots.iter().map(|s| s.weight).sum();
+        let earned: f64 = slots.iter().map(|s| s.earned).sum();
+        let score = if possible > 0.0 {
+            (earned / possible * 100.0).round().clamp(0.0, 100.0) as u8
+        } else {
+            0
+        };
+
+        ScoreBreakdown { slots, score }
+    }
+}
+
+/// Count filled slots as `(filled, total)`
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{parse, slots_filled};
+///
+/// let faf = parse("faf_version: 2.5.0\nproject:\n  name: test").unwrap();
+/// assert_eq!(slots_filled(&faf.data), (1, 24));
+/// ```
+pub fn slots_filled(data: &FafData) -> (usize, usize) {
+    let breakdown = ScoringModel::default().score(data);
+    let filled = breakdown.slots.iter().filter(|s| s.is_complete()).count();
+    (filled, SLOTS.len())
+}
+
+fn lookup<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(root, |value, key| value.as_mapping()?.get(key))
+}
+
+/// How filled a value is, from 0.0 to 1.0
+fn fill_ratio(value: &Value, target: Option<usize>) -> f64 {
+    let count = match value {
+        Value::Null => return 0.0,
+        Value::Bool(b) => return if *b { 1.0 } else { 0.0 },
+        Value::Number(_) => return 1.0,
+        Value::String(s) => return if s.trim().is_empty() { 0.0 } else { 1.0 },
+        Value::Sequence(items) => items.iter().filter(|v| fill_ratio(v, None) > 0.0).count(),
+        Value::Mapping(map) => map.values().filter(|v| fill_ratio(v, None) > 0.0).count(),
+        Value::Tagged(tagged) => return fill_ratio(&tagged.value, target),
+    };
+    match target {
+        Some(target) if target > 0 => (count as f64 / target as f64).min(1.0),
+        _ => {
+            if count > 0 {
+                1.0
+            } else {
+                0.0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_default_model_is_24_slots() {
+        let faf = parse(include_str!("../examples/full.faf")).unwrap();
+        let breakdown = ScoringModel::default().score(&faf.data);
+        assert_eq!(breakdown.slots.len(), 24);
+        // full.faf leaves only project.approach empty
+        assert_eq!(slots_filled(&faf.data), (23, 24));
+        assert_eq!(breakdown.score, 96);
+        assert_eq!(breakdown.next_to_fill()[0].path, "project.approach");
+    }
+
+    #[test]
+    fn test_custom_model_partial_credit() {
+        let model = ScoringModel::from_yaml(
+            r#"
+slots:
+  - path: project.goal
+    weight: 3
+  - path: instant_context.key_files
+    weight: 2
+    target: 4
+  - path: preferences.testing
+  - path: ai_tldr
+"#,
+        )
+        .unwrap();
+
+        let faf = parse(
+            r#"
+faf_version: 2.5.0
+project:
+  name: test
+  goal: Testing
+instant_context:
+  key_files: [a.rs, b.rs]
+preferences:
+  testing: strict
+"#,
+        )
+        .unwrap();
+
+        let breakdown = model.score(&faf.data);
+        assert_eq!(breakdown.possible(), 7.0);
+        assert_eq!(breakdown.earned(), 5.0); // 3 + 2 * 2/4 + 1
+        assert_eq!(breakdown.score, 71);
+
+        let next: Vec<_> = breakdown
+            .next_to_fill()
+            .iter()
+            .map(|s| s.path.as_str())
+            .collect();
+        assert_eq!(next, vec!["instant_context.key_files", "ai_tldr"]);
+    }
+
+    #[test]
+    fn test_invalid_weights_rejected() {
+        for weight in ["-1", ".nan", ".inf", "-.inf"] {
+            let yaml = format!("slots:\n  - path: project.goal\n    weight: {}\n", weight);
+            assert!(
+                matches!(
+                    ScoringModel::from_yaml(&yaml),
+                    Err(FafError::InvalidScoringModel(message)) if message.starts_with("project.goal")
+                ),
+                "weight {} accepted",
+                weight
+            );
+        }
+        assert!(ScoringModel::from_yaml("slots:\n  - path: project.goal\n    weight: 0\n").is_ok());
+    }
+
+    #[test]
+    fn test_renamed_fields_and_empty_model() {
+        let faf = parse("faf_version: 2.5.0\nproject:\n  name: t\nhuman_context:\n  why: Because")
+            .unwrap();
+        let breakdown = ScoringModel::default().score(&faf.data);
+        let why = breakdown
+            .slots
+            .iter()
+            .find(|s| s.path == "human_context.why")
+            .unwrap();
+        assert!(why.is_complete());
+
+        let empty = ScoringModel { slots: Vec::new() };
+        assert_eq!(empty.score(&faf.data).score, 0);
+    }
+}
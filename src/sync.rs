@@ -0,0 +1,418 @@
+//! Bi-directional sync with three-way merge
+//!
+//! Local ("ours") and remote ("theirs") edits are compared field by field
+//! against a shared base snapshot. Edits to different fields merge
+//! automatically; string lists (`tags`, `key_files`) merge as sets. Only a
+//! field changed differently on both sides becomes a [`Conflict`].
+
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::Path;
+
+use crate::parser::FafError;
+use crate::types::FafData;
+
+/// A field changed differently on both sides
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    /// Dotted path using YAML names (`project.goal`)
+    pub path: String,
+    /// Value in the base snapshot (`None` = absent)
+    pub base: Option<Value>,
+    /// Local value
+    pub ours: Option<Value>,
+    /// Remote value
+    pub theirs: Option<Value>,
+}
+
+/// How to settle a conflict
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    /// Keep the local value
+    Ours,
+    /// Take the remote value
+    Theirs,
+    /// Revert to the base value
+    Base,
+    /// Use a different value (`None` removes the field)
+    Value(Option<Value>),
+}
+
+/// Result of a three-way merge
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+    /// Merged data; conflicting fields hold the local value until resolved
+    pub merged: FafData,
+    /// Fields changed differently on both sides
+    pub conflicts: Vec<Conflict>,
+    /// Fields taken from the local side
+    pub from_ours: Vec<String>,
+    /// Fields taken from the remote side
+    pub from_theirs: Vec<String>,
+    tree: Value,
+}
+
+impl MergeResult {
+    /// True if there are no conflicts
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Settle every conflict the same way
+    pub fn resolve(self, strategy: Resolution) -> Result<FafData, FafError> {
+        self.resolve_with(|_| strategy.clone())
+    }
+
+    /// Settle each conflict with a callback
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use faf_rust_sdk::{merge3, parse, Resolution};
+    ///
+    /// let base = parse("faf_version: 2.5.0\nproject:\n  name: app\n  goal: Base").unwrap();
+    /// let ours = parse("faf_version: 2.5.0\nproject:\n  name: app\n  goal: Ours").unwrap();
+    /// let theirs = parse("faf_version: 2.5.0\nproject:\n  name: app\n  goal: Theirs").unwrap();
+    ///
+    /// let result = merge3(&base.data, &ours.data, &theirs.data);
+    /// assert_eq!(result.conflicts[0].path, "project.goal");
+    ///
+    /// let merged = result
+    ///     .resolve_with(|c| if c.path == "project.goal" { Resolution::Theirs } else { Resolution::Ours })
+    ///     .unwrap();
+    /// assert_eq!(merged.project.goal.as_deref(), Some("Theirs"));
+    /// ```
+    pub fn resolve_with<F>(self, mut choose: F) -> Result<FafData, FafError>
+    where
+        F: FnMut(&Conflict) -> Resolution,
+    {
+        let mut tree = self.tree;
+        for conflict in &self.conflicts {
+            let value = match choose(conflict) {
+                Resolution::Ours => conflict.ours.clone(),
+                Resolution::Theirs => conflict.theirs.clone(),
+                Resolution::Base => conflict.base.clone(),
+                Resolution::Value(value) => value,
+            };
+            set_path(&mut tree, &conflict.path, value);
+        }
+        Ok(serde_yaml::from_value(tree)?)
+    }
+}
+
+/// Three-way merge of local and remote edits against a common base
+pub fn merge3(base: &FafData, ours: &FafData, theirs: &FafData) -> MergeResult {
+    let base = to_value(base);
+    let ours_value = to_value(ours);
+    let theirs = to_value(theirs);
+
+    let mut merger = Merger::default();
+    let tree = merger
+        .merge("", Some(&base), Some(&ours_value), Some(&theirs))
+        .unwrap_or(Value::Mapping(Mapping::new()));
+
+    // Merged mappings of valid FafData always deserialize; fall back to
+    // ours rather than panic if that ever changes
+    let merged = serde_yaml::from_value(tree.clone()).unwrap_or_else(|_| ours.clone());
+    MergeResult {
+        merged,
+        conflicts: merger.conflicts,
+        from_ours: merger.from_ours,
+        from_theirs: merger.from_theirs,
+        tree,
+    }
+}
+
+/// Sync state: the last snapshot both sides agreed on
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{parse_file, Resolution, SyncEngine};
+///
+/// let mut engine = SyncEngine::load("project.faf.base").unwrap();
+/// let local = parse_file("project.faf").unwrap();
+/// let remote = parse_file("remote/project.faf").unwrap();
+///
+/// let merged = engine.merge(&local.data, &remote.data).resolve(Resolution::Ours).unwrap();
+/// engine.commit(&merged);
+/// engine.save("project.faf.base").unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SyncEngine {
+    base: FafData,
+}
+
+impl SyncEngine {
+    /// Start from a known base snapshot
+    pub fn new(base: FafData) -> Self {
+        SyncEngine { base }
+    }
+
+    /// Load a base snapshot saved with [`SyncEngine::save`]
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, FafError> {
+        let content = fs::read_to_string(path)?;
+        Ok(SyncEngine::new(serde_yaml::from_str(&content)?))
+    }
+
+    /// Write the base snapshot as YAML
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), FafError> {
+        fs::write(path, serde_yaml::to_string(&self.base)?)?;
+        Ok(())
+    }
+
+    /// Current base snapshot
+    pub fn base(&self) -> &FafData {
+        &self.base
+    }
+
+    /// Merge local and remote edits made since the base
+    pub fn merge(&self, ours: &FafData, theirs: &FafData) -> MergeResult {
+        merge3(&self.base, ours, theirs)
+    }
+
+    /// Record a merged result as the new base
+    pub fn commit(&mut self, merged: &FafData) {
+        self.base = merged.clone();
+    }
+}
+
+fn to_value(data: &FafData) -> Value {
+    serde_yaml::to_value(data).unwrap_or(Value::Null)
+}
+
+#[derive(Default)]
+struct Merger {
+    conflicts: Vec<Conflict>,
+    from_ours: Vec<String>,
+    from_theirs: Vec<String>,
+}
+
+impl Merger {
+    fn merge(
+        &mut self,
+        path: &str,
+        base: Option<&Value>,
+        ours: Option<&Value>,
+        theirs: Option<&Value>,
+    ) -> Option<Value> {
+        let (base, ours, theirs) = (present(base), present(ours), present(theirs));
+
+        if ours == theirs {
+            return ours.cloned();
+        }
+        // Recurse into sections so changes are reported per field
+        if let (None | Some(Value::Mapping(_)), Some(Value::Mapping(o)), Some(Value::Mapping(t))) =
+            (base, ours, theirs)
+        {
+            let empty = Mapping::new();
+            let b = base.and_then(Value::as_mapping).unwrap_or(&empty);
+            let mut out = Mapping::new();
+            let keys = o
+                .keys()
+                .chain(t.keys().filter(|k| !o.contains_key(*k)))
+                .chain(
+                    b.keys()
+                        .filter(|k| !o.contains_key(*k) && !t.contains_key(*k)),
+                );
+            for key in keys {
+                let child = match key.as_str() {
+                    Some(name) if path.is_empty() => name.to_string(),
+                    Some(name) => format!("{}.{}", path, name),
+                    None => continue,
+                };
+                if let Some(value) = self.merge(&child, b.get(key), o.get(key), t.get(key)) {
+                    out.insert(key.clone(), value);
+                }
+            }
+            return Some(Value::Mapping(out));
+        }
+
+        if base == ours {
+            self.from_theirs.push(path.to_string());
+            return theirs.cloned();
+        }
+        if base == theirs {
+            self.from_ours.push(path.to_string());
+            return ours.cloned();
+        }
+
+        match (base, ours, theirs) {
+            (
+                None | Some(Value::Sequence(_)),
+                Some(Value::Sequence(o)),
+                Some(Value::Sequence(t)),
+            ) => {
+                let b = base
+                    .and_then(Value::as_sequence)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let mut out: Vec<Value> = o
+                    .iter()
+                    .filter(|v| !b.contains(v) || t.contains(v))
+                    .cloned()
+                    .collect();
+                for item in t {
+                    if !b.contains(item) && !out.contains(item) {
+                        out.push(item.clone());
+                    }
+                }
+                self.from_ours.push(path.to_string());
+                self.from_theirs.push(path.to_string());
+                Some(Value::Sequence(out))
+            }
+            _ => {
+                self.conflicts.push(Conflict {
+                    path: path.to_string(),
+                    base: base.cloned(),
+                    ours: ours.cloned(),
+                    theirs: theirs.cloned(),
+                });
+                ours.cloned()
+            }
+        }
+    }
+}
+
+/// Treat explicit nulls as absent
+fn present(value: Option<&Value>) -> Option<&Value> {
+    value.filter(|v| !v.is_null())
+}
+
+fn set_path(tree: &mut Value, path: &str, value: Option<Value>) {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (Some(parent), key),
+        None => (None, path),
+    };
+    let mut node = tree;
+    for part in parent.into_iter().flat_map(|p| p.split('.')) {
+        let Some(map) = node.as_mapping_mut() else {
+            return;
+        };
+        node = map
+            .entry(Value::String(part.to_string()))
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+    }
+    if let Some(map) = node.as_mapping_mut() {
+        match value {
+            Some(value) => {
+                map.insert(Value::String(key.to_string()), value);
+            }
+            None => {
+                map.remove(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const BASE: &str = r#"
+faf_version: 2.5.0
+project:
+  name: shared
+  goal: Original goal
+instant_context:
+  what_building: Feature
+  commands:
+    test: cargo test
+tags: [rust, cli]
+"#;
+
+    fn edit(from: &str, to: &str) -> FafData {
+        parse(&BASE.replace(from, to)).unwrap().data
+    }
+
+    #[test]
+    fn test_non_overlapping_edits_merge() {
+        let base = parse(BASE).unwrap().data;
+        let ours = edit("goal: Original goal", "goal: New goal");
+        let mut theirs = edit("test: cargo test", "test: cargo test --all");
+        theirs
+            .instant_context
+            .as_mut()
+            .unwrap()
+            .commands
+            .insert("lint".into(), "cargo clippy".into());
+
+        let result = merge3(&base, &ours, &theirs);
+        assert!(result.is_clean());
+        assert_eq!(result.from_ours, vec!["project.goal"]);
+        assert_eq!(
+            result.from_theirs,
+            vec![
+                "instant_context.commands.test",
+                "instant_context.commands.lint"
+            ]
+        );
+        let merged = result.merged;
+        assert_eq!(merged.project.goal.as_deref(), Some("New goal"));
+        let commands = &merged.instant_context.unwrap().commands;
+        assert_eq!(commands["test"], "cargo test --all");
+        assert_eq!(commands["lint"], "cargo clippy");
+    }
+
+    #[test]
+    fn test_lists_merge_as_sets() {
+        let base = parse(BASE).unwrap().data;
+        let ours = edit("tags: [rust, cli]", "tags: [rust, cli, sdk]");
+        let theirs = edit("tags: [rust, cli]", "tags: [rust, yaml]");
+        let result = merge3(&base, &ours, &theirs);
+        assert!(result.is_clean());
+        assert_eq!(result.merged.tags, vec!["rust", "sdk", "yaml"]);
+    }
+
+    #[test]
+    fn test_conflicts_and_resolution() {
+        let base = parse(BASE).unwrap().data;
+        let ours = edit("goal: Original goal", "goal: Ours");
+        let mut theirs = edit("goal: Original goal", "goal: Theirs");
+        theirs.instant_context = None;
+
+        let result = merge3(&base, &ours, &theirs);
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!(conflict.path, "project.goal");
+        assert_eq!(conflict.base, Some(Value::from("Original goal")));
+        assert_eq!(conflict.ours, Some(Value::from("Ours")));
+        assert_eq!(conflict.theirs, Some(Value::from("Theirs")));
+        // Unresolved conflicts hold our value; their deletion still applies
+        assert_eq!(result.merged.project.goal.as_deref(), Some("Ours"));
+        assert!(result.merged.instant_context.is_none());
+
+        let theirs_wins = result.clone().resolve(Resolution::Theirs).unwrap();
+        assert_eq!(theirs_wins.project.goal.as_deref(), Some("Theirs"));
+        let removed = result.resolve(Resolution::Value(None)).unwrap();
+        assert!(removed.project.goal.is_none());
+    }
+
+    #[test]
+    fn test_engine_snapshot_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("project.faf.base");
+        let mut engine = SyncEngine::new(parse(BASE).unwrap().data);
+        engine.save(&path).unwrap();
+
+        let mut loaded = SyncEngine::load(&path).unwrap();
+        assert_eq!(loaded.base().project.goal.as_deref(), Some("Original goal"));
+
+        let ours = edit("Feature", "Feature A");
+        let merged = loaded.merge(&ours, loaded.base()).merged;
+        loaded.commit(&merged);
+        assert_eq!(
+            loaded
+                .base()
+                .instant_context
+                .as_ref()
+                .unwrap()
+                .what_building
+                .as_deref(),
+            Some("Feature A")
+        );
+        engine.commit(&merged);
+        assert_eq!(engine.base().project.name, "shared");
+    }
+}
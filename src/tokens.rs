@@ -0,0 +1,238 @@
+//! Token counting for context budgets
+
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+use crate::types::FafData;
+
+/// Counts tokens in text
+///
+/// Implement this to plug in a real tokenizer; the SDK ships an offline
+/// heuristic.
+pub trait Tokenizer {
+    /// Number of tokens `text` would use
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+/// Offline token estimate - no vocabulary, no network
+///
+/// Splits text into runs and prices each kind separately: words and
+/// other scripts by UTF-8 bytes, CJK by characters, and punctuation runs
+/// (paths, code, YAML syntax) by symbols.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeuristicTokenizer {
+    /// Average UTF-8 bytes per token for words and non-CJK scripts
+    pub bytes_per_token: f64,
+    /// Average CJK characters (Han, Kana, Hangul) per token
+    pub cjk_chars_per_token: f64,
+    /// Average punctuation characters per token
+    pub symbols_per_token: f64,
+}
+
+impl Default for HeuristicTokenizer {
+    fn default() -> Self {
+        HeuristicTokenizer {
+            bytes_per_token: 4.0,
+            cjk_chars_per_token: 1.0,
+            symbols_per_token: 2.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Run {
+    Space,
+    Word,
+    Cjk,
+    Symbol,
+}
+
+fn classify(c: char) -> Run {
+    match c {
+        c if c.is_whitespace() => Run::Space,
+        '\u{3040}'..='\u{30ff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{ac00}'..='\u{d7af}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{ff00}'..='\u{ffef}' => Run::Cjk,
+        c if c.is_ascii_punctuation() => Run::Symbol,
+        _ => Run::Word,
+    }
+}
+
+impl HeuristicTokenizer {
+    fn price(&self, run: Run, chars: usize, bytes: usize) -> usize {
+        let tokens = match run {
+            Run::Space => return 0,
+            Run::Word => bytes as f64 / self.bytes_per_token,
+            Run::Cjk => chars as f64 / self.cjk_chars_per_token,
+            Run::Symbol => chars as f64 / self.symbols_per_token,
+        };
+        tokens.ceil() as usize
+    }
+}
+
+impl Tokenizer for HeuristicTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        let mut total = 0;
+        let mut current = Run::Space;
+        let (mut chars, mut bytes) = (0, 0);
+
+        for c in text.chars() {
+            let run = classify(c);
+            if run != current {
+                total += self.price(current, chars, bytes);
+                current = run;
+                chars = 0;
+                bytes = 0;
+            }
+            chars += 1;
+            bytes += c.len_utf8();
+        }
+        total + self.price(current, chars, bytes)
+    }
+}
+
+impl<F: Fn(&str) -> usize> Tokenizer for F {
+    fn count_tokens(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+/// Token count of one top-level section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionTokens {
+    /// Top-level YAML key (`project`, `instant_context`, `tags`, ...)
+    pub section: String,
+    pub tokens: usize,
+}
+
+/// Per-section token counts for a FAF document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBreakdown {
+    /// Sections in document order
+    pub sections: Vec<SectionTokens>,
+    /// Tokens for the whole document (may differ slightly from the sum)
+    pub total: usize,
+}
+
+impl TokenBreakdown {
+    /// Tokens for one section, or 0 if absent
+    pub fn section(&self, name: &str) -> usize {
+        self.sections
+            .iter()
+            .find(|s| s.section == name)
+            .map_or(0, |s| s.tokens)
+    }
+}
+
+/// Estimate tokens for FAF data serialized as YAML (offline heuristic)
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{estimate_tokens_for, parse};
+///
+/// let faf = parse("faf_version: 2.5.0\nproject:\n  name: test\n  goal: Testing").unwrap();
+/// let tokens = estimate_tokens_for(&faf.data);
+/// assert!(tokens > 0 && tokens < 50);
+/// ```
+pub fn estimate_tokens_for(data: &FafData) -> usize {
+    estimate_tokens_with(data, &HeuristicTokenizer::default())
+}
+
+/// Estimate tokens for FAF data with a custom tokenizer
+pub fn estimate_tokens_with(data: &FafData, tokenizer: &dyn Tokenizer) -> usize {
+    estimate_value_tokens(data, tokenizer)
+}
+
+/// Estimate tokens for any serializable section (`&faf.data.stack`, ...)
+pub fn estimate_value_tokens<T: Serialize + ?Sized>(value: &T, tokenizer: &dyn Tokenizer) -> usize {
+    tokenizer.count_tokens(&serde_yaml::to_string(value).unwrap_or_default())
+}
+
+/// Token counts per top-level section
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{parse, token_breakdown, HeuristicTokenizer};
+///
+/// let faf = parse("faf_version: 2.5.0\nproject:\n  name: test\ntags: [rust]").unwrap();
+/// let breakdown = token_breakdown(&faf.data, &HeuristicTokenizer::default());
+/// assert!(breakdown.section("project") > 0);
+/// assert_eq!(breakdown.section("stack"), 0);
+/// ```
+pub fn token_breakdown(data: &FafData, tokenizer: &dyn Tokenizer) -> TokenBreakdown {
+    let root = serde_yaml::to_value(data).unwrap_or(Value::Null);
+    let sections = root
+        .as_mapping()
+        .into_iter()
+        .flatten()
+        .map(|(key, value)| {
+            let mut section = Mapping::new();
+            section.insert(key.clone(), value.clone());
+            SectionTokens {
+                section: key.as_str().unwrap_or_default().to_string(),
+                tokens: estimate_value_tokens(&section, tokenizer),
+            }
+        })
+        .collect();
+
+    TokenBreakdown {
+        sections,
+        total: estimate_tokens_with(data, tokenizer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_heuristic_scripts() {
+        let t = HeuristicTokenizer::default();
+        assert_eq!(t.count_tokens(""), 0);
+        assert_eq!(t.count_tokens("   \n"), 0);
+        // Words: 4 bytes per token
+        assert_eq!(t.count_tokens("hello world"), 4);
+        // CJK: one token per character
+        assert_eq!(t.count_tokens("日本語テスト"), 6);
+        // Code: symbol runs priced separately from identifiers
+        assert_eq!(t.count_tokens("src/lib.rs"), 5);
+        // CJK costs more per byte than English
+        assert!(t.count_tokens("日本語テスト") > t.count_tokens("abcdefghijklmnopqr"));
+    }
+
+    #[test]
+    fn test_estimate_tracks_content() {
+        let short = parse("faf_version: 2.5.0\nproject:\n  name: test\n  goal: Go").unwrap();
+        let long_goal = "Build something great ".repeat(100);
+        let long = parse(&format!(
+            "faf_version: 2.5.0\nproject:\n  name: test\n  goal: {}",
+            long_goal
+        ))
+        .unwrap();
+        assert!(estimate_tokens_for(&long.data) > estimate_tokens_for(&short.data) + 300);
+    }
+
+    #[test]
+    fn test_breakdown_and_custom_tokenizer() {
+        let faf = parse(include_str!("../examples/full.faf")).unwrap();
+        let words = |text: &str| text.split_whitespace().count();
+        let breakdown = token_breakdown(&faf.data, &words);
+
+        let names: Vec<_> = breakdown
+            .sections
+            .iter()
+            .map(|s| s.section.as_str())
+            .collect();
+        assert_eq!(names[0], "faf_version");
+        assert!(names.contains(&"human_context"));
+        let sum: usize = breakdown.sections.iter().map(|s| s.tokens).sum();
+        assert_eq!(sum, breakdown.total);
+        assert_eq!(breakdown.total, estimate_tokens_with(&faf.data, &words));
+    }
+}
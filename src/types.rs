@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Complete FAF file structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FafData {
     pub faf_version: String,
     pub project: Project,
@@ -41,7 +41,7 @@ pub struct FafData {
 }
 
 /// Project metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Project {
     pub name: String,
 
@@ -62,7 +62,7 @@ pub struct Project {
 }
 
 /// Instant context for AI
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct InstantContext {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub what_building: Option<String>,
@@ -81,7 +81,7 @@ pub struct InstantContext {
 }
 
 /// Technical stack
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Stack {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub frontend: Option<String>,
@@ -106,7 +106,7 @@ pub struct Stack {
 }
 
 /// Context quality metrics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ContextQuality {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slots_filled: Option<String>,
@@ -122,7 +122,7 @@ pub struct ContextQuality {
 }
 
 /// Human context - the 6 W's
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HumanContext {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub who: Option<String>,
@@ -144,7 +144,7 @@ pub struct HumanContext {
 }
 
 /// Development preferences
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Preferences {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quality_bar: Option<String>,
@@ -160,7 +160,7 @@ pub struct Preferences {
 }
 
 /// Project state
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct State {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub phase: Option<String>,
@@ -1,128 +1,415 @@
 //! FAF validation
 
-use crate::parser::FafFile;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
 
-/// Validation result
-#[derive(Debug, Clone)]
-pub struct ValidationResult {
-    /// True if no errors
-    pub valid: bool,
-    /// Critical errors
-    pub errors: Vec<String>,
-    /// Non-critical warnings
-    pub warnings: Vec<String>,
-    /// Completeness score (0-100)
-    pub score: u8,
+use crate::parser::{FafError, FafFile};
+use crate::rules::builtin_rules;
+use crate::scoring::ScoringModel;
+
+/// Diagnostic severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Suggestion that may point at a problem
+    Hint,
+    /// Non-critical issue
+    Warning,
+    /// Critical issue - the file is invalid
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Hint => write!(f, "hint"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single problem reported by a rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Stable rule id (`FAF001`)
+    pub id: String,
+    /// Rule name (`missing-project-name`)
+    pub name: String,
+    /// Effective severity after configuration
+    pub severity: Severity,
+    /// Human-readable message
+    pub message: String,
+    /// Suggested fix, if the rule knows one
+    pub fix: Option<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.id, self.name, self.message)
+    }
+}
+
+/// What a rule reports; the validator adds id, name and severity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub message: String,
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    pub fn new(message: impl Into<String>) -> Self {
+        Finding {
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    pub fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+/// Extra context available to rules
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuleContext<'a> {
+    /// Directory the FAF file lives in; filesystem rules are skipped when None
+    pub root: Option<&'a Path>,
+    /// Scoring model in use; rules fall back to the default model when None
+    pub scoring: Option<&'a ScoringModel>,
+}
+
+impl RuleContext<'_> {
+    /// Computed score for `faf` under the context's scoring model
+    pub fn score(&self, faf: &FafFile) -> u8 {
+        match self.scoring {
+            Some(model) => model.score(&faf.data).score,
+            None => calculate_score(faf),
+        }
+    }
 }
 
-/// Validate FAF file structure
+/// A validation rule
 ///
 /// # Example
 ///
 /// ```rust
-/// use faf_rust_sdk::{parse, validate};
+/// use faf_rust_sdk::{parse, Finding, Rule, RuleContext, Severity, Validator, FafFile};
 ///
-/// let content = r#"
-/// faf_version: 2.5.0
-/// project:
-///   name: test
-/// "#;
+/// struct NeedsTags;
 ///
-/// let faf = parse(content).unwrap();
-/// let result = validate(&faf);
-/// assert!(result.valid);
+/// impl Rule for NeedsTags {
+///     fn id(&self) -> &str { "ACME001" }
+///     fn name(&self) -> &str { "missing-tags" }
+///     fn default_severity(&self) -> Severity { Severity::Warning }
+///     fn check(&self, faf: &FafFile, _ctx: &RuleContext) -> Vec<Finding> {
+///         if faf.data.tags.is_empty() {
+///             vec![Finding::new("No tags").with_fix("Add tags")]
+///         } else {
+///             Vec::new()
+///         }
+///     }
+/// }
+///
+/// let mut validator = Validator::default();
+/// validator.register(NeedsTags);
+///
+/// let faf = parse("faf_version: 2.5.0\nproject:\n  name: test").unwrap();
+/// let result = validator.validate(&faf);
+/// assert!(result.diagnostics.iter().any(|d| d.id == "ACME001"));
 /// ```
-pub fn validate(faf: &FafFile) -> ValidationResult {
-    let mut errors = Vec::new();
-    let mut warnings = Vec::new();
+pub trait Rule: Send + Sync {
+    /// Stable id used in configs and output (`FAF001`)
+    fn id(&self) -> &str;
+    /// Short kebab-case name (`missing-project-name`)
+    fn name(&self) -> &str;
+    /// Severity used unless configured otherwise
+    fn default_severity(&self) -> Severity;
+    /// Check the file and report findings
+    fn check(&self, faf: &FafFile, ctx: &RuleContext) -> Vec<Finding>;
+}
+
+/// Rule level in a validator config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Hint,
+    Warning,
+    Error,
+}
 
-    // Required fields
-    if faf.data.faf_version.is_empty() {
-        errors.push("Missing faf_version".to_string());
+/// Validator configuration, usually loaded from YAML
+///
+/// Rules are keyed by id or name:
+///
+/// ```yaml
+/// rules:
+///   FAF007: off
+///   missing-stack: hint
+///   FAF101: error
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ValidatorConfig {
+    #[serde(default)]
+    pub rules: HashMap<String, RuleLevel>,
+}
+
+impl ValidatorConfig {
+    /// Parse config from a YAML string
+    pub fn from_yaml(content: &str) -> Result<Self, FafError> {
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        Ok(serde_yaml::from_str(content)?)
     }
 
-    if faf.data.project.name.is_empty() {
-        errors.push("Missing project.name".to_string());
+    /// Load config from a YAML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, FafError> {
+        Self::from_yaml(&fs::read_to_string(path)?)
     }
+}
 
-    // Recommended sections
-    if faf.data.instant_context.is_none() {
-        warnings.push("Missing instant_context section".to_string());
-    } else {
-        let ic = faf.data.instant_context.as_ref().unwrap();
-        if ic.what_building.is_none() {
-            warnings.push("Missing instant_context.what_building".to_string());
+struct RuleEntry {
+    rule: Box<dyn Rule>,
+    severity: Severity,
+    enabled: bool,
+}
+
+/// Rule-based validator
+///
+/// `Validator::default()` holds the built-in rules; `Validator::new()` is
+/// empty.
+pub struct Validator {
+    rules: Vec<RuleEntry>,
+    scoring: ScoringModel,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        let mut validator = Validator::new();
+        for rule in builtin_rules() {
+            validator.register_boxed(rule);
         }
-        if ic.tech_stack.is_none() {
-            warnings.push("Missing instant_context.tech_stack".to_string());
+        validator
+    }
+}
+
+impl Validator {
+    /// Create a validator with no rules
+    pub fn new() -> Self {
+        Validator {
+            rules: Vec::new(),
+            scoring: ScoringModel::default(),
         }
     }
 
-    if faf.data.stack.is_none() {
-        warnings.push("Missing stack section".to_string());
+    /// Use a custom scoring model for `ValidationResult::score`
+    pub fn set_scoring_model(&mut self, model: ScoringModel) -> &mut Self {
+        self.scoring = model;
+        self
     }
 
-    if faf.data.human_context.is_none() {
-        warnings.push("Missing human_context section".to_string());
+    /// Register a rule (replaces an existing rule with the same id)
+    pub fn register<R: Rule + 'static>(&mut self, rule: R) -> &mut Self {
+        self.register_boxed(Box::new(rule))
     }
 
-    // Calculate score
-    let score = calculate_score(faf);
+    /// Register a boxed rule (replaces an existing rule with the same id)
+    pub fn register_boxed(&mut self, rule: Box<dyn Rule>) -> &mut Self {
+        let entry = RuleEntry {
+            severity: rule.default_severity(),
+            enabled: true,
+            rule,
+        };
+        match self.position(entry.rule.id()) {
+            Some(i) => self.rules[i] = entry,
+            None => self.rules.push(entry),
+        }
+        self
+    }
 
-    ValidationResult {
-        valid: errors.is_empty(),
-        errors,
-        warnings,
-        score,
+    /// Enable a rule by id or name; returns false if unknown
+    pub fn enable(&mut self, rule: &str) -> bool {
+        self.entry_mut(rule).map(|e| e.enabled = true).is_some()
     }
-}
 
-fn calculate_score(faf: &FafFile) -> u8 {
-    let mut score: u8 = 0;
+    /// Disable a rule by id or name; returns false if unknown
+    pub fn disable(&mut self, rule: &str) -> bool {
+        self.entry_mut(rule).map(|e| e.enabled = false).is_some()
+    }
 
-    // Required fields (30 points)
-    if !faf.data.faf_version.is_empty() {
-        score += 10;
+    /// Change a rule's severity by id or name; returns false if unknown
+    pub fn set_severity(&mut self, rule: &str, severity: Severity) -> bool {
+        self.entry_mut(rule)
+            .map(|e| e.severity = severity)
+            .is_some()
     }
-    if !faf.data.project.name.is_empty() {
-        score += 10;
+
+    /// Apply a config; fails on the first unknown rule without changing anything
+    pub fn configure(&mut self, config: &ValidatorConfig) -> Result<(), FafError> {
+        if let Some(unknown) = config.rules.keys().find(|k| self.position(k).is_none()) {
+            return Err(FafError::UnknownRule(unknown.clone()));
+        }
+        for (rule, level) in &config.rules {
+            match level {
+                RuleLevel::Off => self.disable(rule),
+                RuleLevel::Hint => self.enable(rule) && self.set_severity(rule, Severity::Hint),
+                RuleLevel::Warning => {
+                    self.enable(rule) && self.set_severity(rule, Severity::Warning)
+                }
+                RuleLevel::Error => self.enable(rule) && self.set_severity(rule, Severity::Error),
+            };
+        }
+        Ok(())
     }
-    if faf.data.project.goal.is_some() {
-        score += 10;
+
+    /// Registered rules with their effective severity and enabled flag
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, Severity, bool)> {
+        self.rules
+            .iter()
+            .map(|e| (e.rule.as_ref(), e.severity, e.enabled))
     }
 
-    // Instant context (30 points)
-    if let Some(ic) = &faf.data.instant_context {
-        if ic.what_building.is_some() {
-            score += 10;
-        }
-        if ic.tech_stack.is_some() {
-            score += 10;
+    /// Run all enabled rules except filesystem checks
+    pub fn validate(&self, faf: &FafFile) -> ValidationResult {
+        self.run(faf, None)
+    }
+
+    /// Run all enabled rules, resolving paths relative to `root`
+    pub fn validate_against_fs<P: AsRef<Path>>(&self, faf: &FafFile, root: P) -> ValidationResult {
+        self.run(faf, Some(root.as_ref()))
+    }
+
+    fn run(&self, faf: &FafFile, root: Option<&Path>) -> ValidationResult {
+        let ctx = RuleContext {
+            root,
+            scoring: Some(&self.scoring),
+        };
+        let mut diagnostics = Vec::new();
+        for entry in self.rules.iter().filter(|e| e.enabled) {
+            for finding in entry.rule.check(faf, &ctx) {
+                diagnostics.push(Diagnostic {
+                    id: entry.rule.id().to_string(),
+                    name: entry.rule.name().to_string(),
+                    severity: entry.severity,
+                    message: finding.message,
+                    fix: finding.fix,
+                });
+            }
         }
-        if !ic.key_files.is_empty() {
-            score += 10;
+
+        let messages = |severity| {
+            diagnostics
+                .iter()
+                .filter(|d: &&Diagnostic| d.severity == severity)
+                .map(|d| d.message.clone())
+                .collect()
+        };
+        ValidationResult {
+            valid: !diagnostics.iter().any(|d| d.severity == Severity::Error),
+            errors: messages(Severity::Error),
+            warnings: messages(Severity::Warning),
+            diagnostics,
+            score: self.scoring.score(&faf.data).score,
         }
     }
 
-    // Stack (15 points)
-    if faf.data.stack.is_some() {
-        score += 15;
+    fn position(&self, rule: &str) -> Option<usize> {
+        self.rules
+            .iter()
+            .position(|e| e.rule.id() == rule || e.rule.name() == rule)
     }
 
-    // Human context (15 points)
-    if faf.data.human_context.is_some() {
-        score += 15;
+    fn entry_mut(&mut self, rule: &str) -> Option<&mut RuleEntry> {
+        self.position(rule).map(|i| &mut self.rules[i])
     }
+}
 
-    // Extras (10 points)
-    if !faf.data.tags.is_empty() {
-        score += 5;
-    }
-    if faf.data.state.is_some() {
-        score += 5;
+/// Validation result
+#[derive(Debug, Clone)]
+pub struct ValidationResult {
+    /// True if no errors
+    pub valid: bool,
+    /// Critical errors
+    pub errors: Vec<String>,
+    /// Non-critical warnings
+    pub warnings: Vec<String>,
+    /// Everything the enabled rules reported, with rule ids and fixes
+    pub diagnostics: Vec<Diagnostic>,
+    /// Completeness score (0-100)
+    pub score: u8,
+}
+
+impl ValidationResult {
+    /// Diagnostics of one severity
+    pub fn diagnostics_with(&self, severity: Severity) -> Vec<&Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .collect()
     }
+}
 
-    score.min(100)
+/// Validate FAF file structure with the built-in rules
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{parse, validate};
+///
+/// let content = r#"
+/// faf_version: 2.5.0
+/// project:
+///   name: test
+/// "#;
+///
+/// let faf = parse(content).unwrap();
+/// let result = validate(&faf);
+/// assert!(result.valid);
+/// ```
+pub fn validate(faf: &FafFile) -> ValidationResult {
+    Validator::default().validate(faf)
+}
+
+/// Validate FAF file structure and check it against the filesystem
+///
+/// Runs the built-in rules, then resolves `instant_context.key_files`
+/// relative to `root` (normally the FAF file's directory). Missing files and
+/// glob patterns matching nothing become warnings. Commands that invoke a
+/// build tool whose manifest is absent from `root` (`npm test` without a
+/// package.json) become hints.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{parse_file, validate_against_fs};
+///
+/// let faf = parse_file("project.faf").unwrap();
+/// let result = validate_against_fs(&faf, ".");
+/// for warning in &result.warnings {
+///     println!("{}", warning);
+/// }
+/// ```
+pub fn validate_against_fs<P: AsRef<Path>>(faf: &FafFile, root: P) -> ValidationResult {
+    Validator::default().validate_against_fs(faf, root)
+}
+
+/// Compute the completeness score (0-100) with the default 24-slot model
+///
+/// # Example
+///
+/// ```rust
+/// use faf_rust_sdk::{calculate_score, parse};
+///
+/// let faf = parse("faf_version: 2.5.0\nproject:\n  name: test").unwrap();
+/// assert_eq!(calculate_score(&faf), 4); // 1 of 24 slots
+/// ```
+pub fn calculate_score(faf: &FafFile) -> u8 {
+    ScoringModel::default().score(&faf.data).score
 }
 
 #[cfg(test)]
@@ -140,7 +427,8 @@ project:
         let faf = parse(content).unwrap();
         let result = validate(&faf);
         assert!(result.valid);
-        assert!(result.score >= 20);
+        // project.name is 1 of 24 slots
+        assert_eq!(result.score, 4);
     }
 
     #[test]
@@ -150,15 +438,33 @@ faf_version: 2.5.0
 project:
   name: test
   goal: Testing
+  main_language: Rust
+  approach: TDD
+  version: 1.0.0
+  license: MIT
 instant_context:
   what_building: Test
   tech_stack: Rust
+  deployment: crates.io
   key_files:
     - main.rs
+  commands:
+    test: cargo test
 stack:
+  frontend: None
   backend: Rust
+  database: SQLite
+  infrastructure: GitHub
+  build_tool: Cargo
+  testing: cargo test
+  cicd: GitHub Actions
 human_context:
   who: Developers
+  what: Test app
+  why: Testing
+  how: Carefully
+  where: GitHub
+  when: 2025
 tags:
   - rust
 state:
@@ -167,6 +473,120 @@ state:
         let faf = parse(content).unwrap();
         let result = validate(&faf);
         assert!(result.valid);
-        assert!(result.score >= 90);
+        assert_eq!(result.score, 100);
+    }
+
+    #[test]
+    fn test_custom_scoring_model() {
+        let faf = parse("faf_version: 2.5.0\nproject:\n  name: test\n  goal: Testing").unwrap();
+        let model = ScoringModel::from_yaml("slots:\n  - path: project.goal").unwrap();
+        let mut validator = Validator::default();
+        validator.set_scoring_model(model);
+        assert_eq!(validator.validate(&faf).score, 100);
+        assert_eq!(calculate_score(&faf), 8);
+    }
+
+    #[test]
+    fn test_validate_against_fs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        // Glob metacharacters in the root are taken literally
+        let root = dir.path().join("repo [1]");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir(root.join("web")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "").unwrap();
+        std::fs::write(root.join("web/package.json"), "{}").unwrap();
+
+        let content = r#"
+faf_version: 2.5.0
+project:
+  name: test
+instant_context:
+  key_files:
+    - src/lib.rs
+    - src/gone.rs
+    - src/*.rs
+    - docs/*.md
+  commands:
+    test: cargo test
+    web: cd web && NODE_ENV=test npm test
+    lint: npm run lint
+    env: cd $APP_DIR && npm test
+"#;
+        let faf = parse(content).unwrap();
+        let result = validate_against_fs(&faf, &root);
+        assert!(result.valid);
+        let warnings = result.diagnostics_with(Severity::Warning);
+        assert!(warnings
+            .iter()
+            .any(|w| w.id == "FAF101" && w.message.contains("src/gone.rs")));
+        assert!(warnings
+            .iter()
+            .any(|w| w.id == "FAF102" && w.message.contains("docs/*.md")));
+        assert!(!warnings
+            .iter()
+            .any(|w| w.message.contains("src/lib.rs") || w.message.contains("src/*.rs")));
+        let hints = result.diagnostics_with(Severity::Hint);
+        // web/package.json serves `cd web && npm test`; the root has none
+        assert_eq!(hints.len(), 1);
+        assert!(hints[0].message.starts_with("commands.lint uses 'npm'"));
+        assert!(hints[0].message.contains("package.json"));
+    }
+
+    #[test]
+    fn test_diagnostics_have_rule_ids() {
+        let faf = parse("faf_version: 2.5.0\nproject:\n  name: \"\"").unwrap();
+        let result = validate(&faf);
+        assert!(!result.valid);
+        assert_eq!(result.errors, ["Missing project.name"]);
+        assert_eq!(result.warnings.len(), result.diagnostics_with(Severity::Warning).len());
+        let error = result.diagnostics_with(Severity::Error)[0];
+        assert_eq!(error.id, "FAF001");
+        assert_eq!(error.name, "missing-project-name");
+        assert!(error.fix.is_some());
+        assert_eq!(
+            error.to_string(),
+            "FAF001 missing-project-name: Missing project.name"
+        );
+    }
+
+    #[test]
+    fn test_disable_and_set_severity() {
+        let faf = parse("faf_version: 2.5.0\nproject:\n  name: \"\"").unwrap();
+        let mut validator = Validator::default();
+        assert!(validator.set_severity("FAF001", Severity::Warning));
+        assert!(validator.disable("missing-human-context"));
+        assert!(!validator.disable("FAF999"));
+
+        let result = validator.validate(&faf);
+        assert!(result.valid);
+        assert!(result.diagnostics_with(Severity::Warning).iter().any(|d| d.id == "FAF001"));
+        assert!(!result.diagnostics.iter().any(|d| d.id == "FAF007"));
+    }
+
+    #[test]
+    fn test_configure_from_yaml() {
+        let config = ValidatorConfig::from_yaml(
+            r#"
+rules:
+  FAF006: off
+  missing-human-context: error
+"#,
+        )
+        .unwrap();
+        let mut validator = Validator::default();
+        validator.configure(&config).unwrap();
+
+        let faf = parse("faf_version: 2.5.0\nproject:\n  name: test").unwrap();
+        let result = validator.validate(&faf);
+        assert!(!result.valid);
+        assert!(!result.diagnostics.iter().any(|d| d.id == "FAF006"));
+        assert_eq!(result.diagnostics_with(Severity::Error)[0].id, "FAF007");
+
+        let bad = ValidatorConfig::from_yaml("rules:\n  FAF999: off").unwrap();
+        assert!(matches!(
+            validator.configure(&bad),
+            Err(FafError::UnknownRule(id)) if id == "FAF999"
+        ));
     }
 }
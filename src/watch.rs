@@ -0,0 +1,572 @@
+//! Re-parse a FAF file when it changes (`watch` feature)
+//!
+//! Watches the directories holding the file (and, with
+//! [`WatchOptions::layered`], every directory a parent layer can live in)
+//! so editors that replace files by rename are seen. Bursts of events are
+//! debounced, and a file is only read once two reads `settle` apart agree,
+//! so a half-written file is never reported as valid. The current state is
+//! sent first; after that an event is sent only when the state changes.
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::Duration;
+
+use crate::discovery::{find_faf_file, FindError, FAF_FILES, MAX_DEPTH};
+use crate::layered::find_and_parse_layered;
+use crate::parser::{content_hash, parse, FafError, FafFile};
+use crate::validator::{validate, Diagnostic, Severity};
+
+/// A change in the watched file
+#[derive(Debug, Clone)]
+pub enum FafEvent {
+    /// The file parsed and validated
+    Updated(Box<FafFile>),
+    /// The file could not be read (`FAF100 read-error`), failed to parse
+    /// (`FAF000 parse-error`) or has error diagnostics
+    Invalid(Vec<Diagnostic>),
+    /// The file no longer exists
+    Removed,
+}
+
+/// Watcher settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// Quiet period that ends a burst of changes (default 100ms)
+    pub debounce: Duration,
+    /// Gap between the two reads that must agree (default 50ms)
+    pub settle: Duration,
+    /// Merge parent layers as [`find_and_parse_layered`] does, and watch them
+    /// too (including layers and `extends` targets added later)
+    pub layered: bool,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        WatchOptions {
+            debounce: Duration::from_millis(100),
+            settle: Duration::from_millis(50),
+            layered: false,
+        }
+    }
+}
+
+/// A running watch from [`watch`]; dropping it stops watching
+pub struct FafWatcher {
+    path: PathBuf,
+    events: Receiver<FafEvent>,
+    _watcher: Arc<Mutex<RecommendedWatcher>>,
+}
+
+impl FafWatcher {
+    /// The watched file (the nearest layer when layered)
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Wait for the next event; `None` once the watcher has stopped
+    pub fn recv(&self) -> Option<FafEvent> {
+        self.events.recv().ok()
+    }
+
+    /// Wait up to `timeout` for the next event
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<FafEvent> {
+        self.events.recv_timeout(timeout).ok()
+    }
+
+    /// The next event, if one is waiting
+    pub fn try_recv(&self) -> Option<FafEvent> {
+        self.events.try_recv().ok()
+    }
+}
+
+/// A running watch from [`watch_with`]; dropping it stops watching
+///
+/// Events go to the callback, so there is nothing to receive here.
+pub struct WatchHandle {
+    path: PathBuf,
+    _watcher: Arc<Mutex<RecommendedWatcher>>,
+}
+
+impl WatchHandle {
+    /// The watched file (the nearest layer when layered)
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Watch a FAF file, receiving events over a channel
+///
+/// `path` is a FAF file, or a directory to discover one from.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{watch, FafEvent, WatchOptions};
+///
+/// let watcher = watch(".", &WatchOptions::default()).unwrap();
+/// while let Some(event) = watcher.recv() {
+///     match event {
+///         FafEvent::Updated(faf) => println!("reloaded {}", faf.project_name()),
+///         FafEvent::Invalid(diagnostics) => eprintln!("{} problems", diagnostics.len()),
+///         FafEvent::Removed => eprintln!("project.faf removed"),
+///     }
+/// }
+/// ```
+pub fn watch<P: AsRef<Path>>(path: P, options: &WatchOptions) -> Result<FafWatcher, FafError> {
+    let (tx, events) = mpsc::channel();
+    let (path, watcher) = start(path.as_ref(), options, move |event| tx.send(event).is_ok())?;
+    Ok(FafWatcher {
+        path,
+        events,
+        _watcher: watcher,
+    })
+}
+
+/// Watch a FAF file, calling `callback` for each event
+///
+/// The callback runs on a background thread; return `false` to stop.
+/// Events stop when the returned handle is dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{watch_with, FafEvent, WatchOptions};
+///
+/// let _handle = watch_with("project.faf", &WatchOptions::default(), |event| {
+///     if let FafEvent::Updated(faf) = event {
+///         println!("reloaded {}", faf.project_name());
+///     }
+///     true
+/// })
+/// .unwrap();
+/// ```
+pub fn watch_with<P, F>(
+    path: P,
+    options: &WatchOptions,
+    callback: F,
+) -> Result<WatchHandle, FafError>
+where
+    P: AsRef<Path>,
+    F: FnMut(FafEvent) -> bool + Send + 'static,
+{
+    let (path, watcher) = start(path.as_ref(), options, callback)?;
+    Ok(WatchHandle {
+        path,
+        _watcher: watcher,
+    })
+}
+
+type SharedWatcher = Arc<Mutex<RecommendedWatcher>>;
+
+fn start<F>(
+    path: &Path,
+    options: &WatchOptions,
+    mut emit: F,
+) -> Result<(PathBuf, SharedWatcher), FafError>
+where
+    F: FnMut(FafEvent) -> bool + Send + 'static,
+{
+    let file = if path.is_dir() {
+        find_faf_file(Some(path))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no FAF file found"))?
+    } else {
+        path.to_path_buf()
+    };
+    let file = fs::canonicalize(&file)?;
+    let dir = file.parent().unwrap_or(Path::new("/")).to_path_buf();
+
+    let (raw_tx, raw_rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(raw_tx).map_err(watch_error)?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(watch_error)?;
+    let watcher = Arc::new(Mutex::new(watcher));
+
+    // The thread only holds a weak reference, so dropping the handle drops
+    // the watcher, which disconnects `raw_rx` and ends the thread
+    let state = State {
+        file: file.clone(),
+        options: options.clone(),
+        watcher: Arc::downgrade(&watcher),
+        dirs: vec![dir],
+        files: vec![file.clone()],
+        last: None,
+    };
+    thread::spawn(move || state.run(raw_rx, &mut emit));
+    Ok((file, watcher))
+}
+
+fn watch_error(error: notify::Error) -> FafError {
+    match error.kind {
+        notify::ErrorKind::Io(e) => FafError::IoError(e),
+        _ => FafError::IoError(io::Error::other(error.to_string())),
+    }
+}
+
+/// Each watched file and its content (`None` when missing)
+type Contents = Vec<(PathBuf, Option<String>)>;
+
+struct State {
+    /// Nearest file
+    file: PathBuf,
+    options: WatchOptions,
+    watcher: Weak<Mutex<RecommendedWatcher>>,
+    /// Directories being watched
+    dirs: Vec<PathBuf>,
+    /// Every file whose change matters, as of the last check
+    files: Vec<PathBuf>,
+    /// Contents behind the last event (`None` for a missing file), or the
+    /// read error it reported
+    last: Option<Result<Contents, String>>,
+}
+
+impl State {
+    fn run(
+        mut self,
+        raw: Receiver<notify::Result<notify::Event>>,
+        emit: &mut dyn FnMut(FafEvent) -> bool,
+    ) {
+        loop {
+            if let Some(event) = self.check() {
+                if !emit(event) {
+                    return;
+                }
+            }
+            // Wait for a change to one of our files...
+            loop {
+                match raw.recv() {
+                    Ok(Ok(event)) if event.paths.iter().any(|p| self.concerns(p)) => break,
+                    Ok(_) => {}
+                    Err(_) => return,
+                }
+            }
+            // ...then for the burst to end
+            loop {
+                match raw.recv_timeout(self.options.debounce) {
+                    Ok(_) => {}
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        }
+    }
+
+    fn concerns(&self, path: &Path) -> bool {
+        if self.files.iter().any(|f| f == path) {
+            return true;
+        }
+        // A new layer appearing in a watched directory
+        self.options.layered
+            && path
+                .parent()
+                .is_some_and(|p| self.dirs.iter().any(|d| d == p))
+            && path
+                .file_name()
+                .is_some_and(|n| FAF_FILES.iter().any(|f| n == *f))
+    }
+
+    /// Re-resolve the layers and watch any directory not yet watched
+    fn resolve(&mut self) {
+        if !self.options.layered {
+            return;
+        }
+        self.files = find_and_parse_layered(self.file.parent())
+            .map(|layered| layered.layers)
+            .unwrap_or_else(|_| vec![self.file.clone()]);
+        let mut wanted = layer_dirs(&self.file);
+        wanted.extend(
+            self.files
+                .iter()
+                .filter_map(|f| f.parent().map(Path::to_path_buf)),
+        );
+
+        let Some(watcher) = self.watcher.upgrade() else {
+            return;
+        };
+        let Ok(mut watcher) = watcher.lock() else {
+            return;
+        };
+        for dir in wanted {
+            if !self.dirs.contains(&dir) && watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok()
+            {
+                self.dirs.push(dir);
+            }
+        }
+    }
+
+    /// The event for the current state, if it changed since the last one
+    fn check(&mut self) -> Option<FafEvent> {
+        self.resolve();
+        let read: Result<Contents, (PathBuf, io::Error)> = self
+            .files
+            .iter()
+            .map(|f| {
+                read_settled(f, self.options.settle)
+                    .map(|c| (f.clone(), c))
+                    .map_err(|e| (f.clone(), e))
+            })
+            .collect();
+        let contents = match read {
+            Ok(contents) => contents,
+            Err((path, error)) => {
+                let message = format!("cannot read {}: {}", path.display(), error);
+                if self.last.as_ref() == Some(&Err(message.clone())) {
+                    return None;
+                }
+                self.last = Some(Err(message.clone()));
+                return Some(FafEvent::Invalid(vec![read_error(message)]));
+            }
+        };
+        if self.last.as_ref() == Some(&Ok(contents.clone())) {
+            return None;
+        }
+
+        let event = if contents[0].1.is_none() {
+            FafEvent::Removed
+        } else if self.options.layered {
+            match find_and_parse_layered(self.file.parent()) {
+                Ok(layered) => checked(layered.faf),
+                Err(FindError::NotFound) => FafEvent::Removed,
+                Err(e) => FafEvent::Invalid(vec![parse_error(e.to_string())]),
+            }
+        } else {
+            let content = contents[0].1.as_deref().unwrap_or_default();
+            match parse(content) {
+                Ok(mut faf) => {
+                    faf.path = Some(self.file.to_string_lossy().to_string());
+                    faf.set_content_hash(Some(content_hash(content)));
+                    checked(faf)
+                }
+                Err(e) => FafEvent::Invalid(vec![parse_error(e.to_string())]),
+            }
+        };
+        self.last = Some(Ok(contents));
+        Some(event)
+    }
+}
+
+/// Directories a parent layer can appear in: up to the repository root, or
+/// only the file's own outside a repository (as [`find_and_parse_layered`])
+fn layer_dirs(file: &Path) -> Vec<PathBuf> {
+    let Some(dir) = file.parent() else {
+        return Vec::new();
+    };
+    let mut dirs = Vec::new();
+    for ancestor in dir.ancestors().take(MAX_DEPTH) {
+        dirs.push(ancestor.to_path_buf());
+        if ancestor.join(".git").exists() {
+            return dirs;
+        }
+    }
+    vec![dir.to_path_buf()]
+}
+
+/// Read until two reads `settle` apart agree; `None` if the file is gone
+fn read_settled(path: &Path, settle: Duration) -> io::Result<Option<String>> {
+    let read = |path: &Path| match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    };
+    let mut previous = read(path)?;
+    loop {
+        thread::sleep(settle);
+        let current = read(path)?;
+        if current == previous {
+            return Ok(current);
+        }
+        previous = current;
+    }
+}
+
+fn checked(faf: FafFile) -> FafEvent {
+    let result = validate(&faf);
+    if result.valid {
+        FafEvent::Updated(Box::new(faf))
+    } else {
+        FafEvent::Invalid(
+            result
+                .diagnostics_with(Severity::Error)
+                .into_iter()
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+fn read_error(message: String) -> Diagnostic {
+    Diagnostic {
+        id: "FAF100".to_string(),
+        name: "read-error".to_string(),
+        severity: Severity::Error,
+        message,
+        fix: None,
+    }
+}
+
+fn parse_error(message: String) -> Diagnostic {
+    Diagnostic {
+        id: "FAF000".to_string(),
+        name: "parse-error".to_string(),
+        severity: Severity::Error,
+        message,
+        fix: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const VALID: &str = "faf_version: 2.5.0\nproject:\n  name: watched\n  goal: Watch me\n";
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn options() -> WatchOptions {
+        WatchOptions {
+            debounce: Duration::from_millis(50),
+            settle: Duration::from_millis(20),
+            layered: false,
+        }
+    }
+
+    #[test]
+    fn test_watch_updates_invalid_removed() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.faf");
+        fs::write(&path, VALID).unwrap();
+
+        let watcher = watch(dir.path(), &options()).unwrap();
+        assert!(
+            matches!(watcher.recv_timeout(TIMEOUT), Some(FafEvent::Updated(f)) if f.project_name() == "watched")
+        );
+
+        fs::write(&path, VALID.replace("watched", "renamed")).unwrap();
+        assert!(
+            matches!(watcher.recv_timeout(TIMEOUT), Some(FafEvent::Updated(f)) if f.project_name() == "renamed")
+        );
+
+        fs::write(&path, "faf_version: 2.5.0\nproject: [").unwrap();
+        match watcher.recv_timeout(TIMEOUT) {
+            Some(FafEvent::Invalid(diagnostics)) => assert_eq!(diagnostics[0].id, "FAF000"),
+            other => panic!("expected invalid, got {:?}", other),
+        }
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            watcher.recv_timeout(TIMEOUT),
+            Some(FafEvent::Removed)
+        ));
+    }
+
+    #[test]
+    fn test_watch_reports_read_errors() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.faf");
+        fs::write(&path, VALID).unwrap();
+        let watcher = watch(&path, &options()).unwrap();
+        assert!(matches!(
+            watcher.recv_timeout(TIMEOUT),
+            Some(FafEvent::Updated(_))
+        ));
+
+        // Still there, but no longer readable as a file
+        fs::remove_file(&path).unwrap();
+        fs::create_dir(&path).unwrap();
+        loop {
+            match watcher.recv_timeout(TIMEOUT) {
+                Some(FafEvent::Removed) => continue,
+                Some(FafEvent::Invalid(diagnostics)) => {
+                    assert_eq!(diagnostics.len(), 1);
+                    assert_eq!(diagnostics[0].id, "FAF100");
+                    assert!(diagnostics[0].message.starts_with("cannot read "));
+                    break;
+                }
+                other => panic!("expected read error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_watch_debounces_bursts() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.faf");
+        fs::write(&path, VALID).unwrap();
+        let watcher = watch(&path, &options()).unwrap();
+        assert!(matches!(
+            watcher.recv_timeout(TIMEOUT),
+            Some(FafEvent::Updated(_))
+        ));
+
+        // Like test_rapid_modification_resilience: many writes, including
+        // truncated ones that would parse as a different (smaller) file
+        for i in 0..100 {
+            let content = VALID.replace("watched", &format!("v{}", i));
+            let cut = if i % 2 == 0 { content.len() } else { 30 };
+            fs::write(&path, &content[..cut]).unwrap();
+        }
+        fs::write(&path, VALID.replace("watched", "final")).unwrap();
+
+        let mut names = Vec::new();
+        while let Some(event) = watcher.recv_timeout(Duration::from_millis(1000)) {
+            match event {
+                FafEvent::Updated(faf) => names.push(faf.project_name().to_string()),
+                other => panic!("half-written file reported: {:?}", other),
+            }
+        }
+        assert!(names.len() < 10, "not debounced: {:?}", names);
+        assert_eq!(names.last().map(String::as_str), Some("final"));
+    }
+
+    #[test]
+    fn test_watch_with_sees_new_parent_layer() {
+        let dir = TempDir::new().unwrap();
+        let root = fs::canonicalize(dir.path()).unwrap();
+        let package = root.join("packages/api");
+        fs::create_dir_all(&package).unwrap();
+        fs::create_dir(root.join(".git")).unwrap();
+        fs::write(package.join("project.faf"), VALID).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let options = WatchOptions {
+            layered: true,
+            ..options()
+        };
+        let handle = watch_with(&package, &options, move |event| tx.send(event).is_ok()).unwrap();
+        assert_eq!(handle.path(), package.join("project.faf"));
+        match rx.recv_timeout(TIMEOUT) {
+            Ok(FafEvent::Updated(faf)) => assert!(faf.data.human_context.is_none()),
+            other => panic!("expected update, got {:?}", other),
+        }
+
+        // A root layer created after the watch started is merged in
+        fs::write(
+            root.join("project.faf"),
+            "faf_version: 2.5.0\nproject:\n  name: org\nhuman_context:\n  who: Platform team\n",
+        )
+        .unwrap();
+        match rx.recv_timeout(TIMEOUT) {
+            Ok(FafEvent::Updated(faf)) => {
+                assert_eq!(faf.project_name(), "watched");
+                assert_eq!(
+                    faf.data.human_context.unwrap().who.as_deref(),
+                    Some("Platform team")
+                );
+            }
+            other => panic!("expected merged update, got {:?}", other),
+        }
+
+        drop(handle);
+        fs::write(
+            package.join("project.faf"),
+            VALID.replace("watched", "gone"),
+        )
+        .unwrap();
+        assert!(rx.recv_timeout(Duration::from_millis(500)).is_err());
+    }
+}
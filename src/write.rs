@@ -0,0 +1,259 @@
+//! Safe writes of FAF files
+//!
+//! The new content goes to a temporary file in the same directory, is
+//! fsynced, then renamed over the target, so readers see the old file or the
+//! new one and never a mix. Writers that take the lock are serialized on a
+//! lock file in the system temp directory, named after the target's full
+//! path, so nothing is left next to the target. The lock is advisory, so
+//! other programs can still write.
+
+use fs4::fs_std::FileExt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::parser::{content_hash, stringify, FafError, FafFile};
+
+/// Distinguishes temporary files of concurrent writes within one process
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// How [`write_file`] writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Copy the previous file to `<name>.bak` (e.g. `project.faf.bak`) first
+    pub backup: bool,
+    /// Hold an advisory lock while writing (default true)
+    pub lock: bool,
+    /// Fail with [`FafError::ChangedOnDisk`] unless the file still matches
+    /// [`FafFile::content_hash`] (no hash: the file must not exist)
+    pub compare_and_swap: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        WriteOptions {
+            backup: false,
+            lock: true,
+            compare_and_swap: false,
+        }
+    }
+}
+
+/// Write a FAF file atomically, returning the hash of the written content
+///
+/// Store the returned hash with [`FafFile::set_content_hash`] to keep using
+/// compare-and-swap on the next write.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::{parse_file, write_file, WriteOptions};
+///
+/// let mut faf = parse_file("project.faf").unwrap();
+/// faf.data.tags.push("rust".to_string());
+///
+/// let options = WriteOptions {
+///     backup: true,
+///     compare_and_swap: true,
+///     ..WriteOptions::default()
+/// };
+/// let hash = write_file(&faf, "project.faf", &options).unwrap();
+/// faf.set_content_hash(Some(hash));
+/// ```
+pub fn write_file<P: AsRef<Path>>(
+    faf: &FafFile,
+    path: P,
+    options: &WriteOptions,
+) -> Result<[u8; 32], FafError> {
+    let path = path.as_ref();
+    let (dir, name) = split_path(path)?;
+    let content = stringify(faf)?;
+
+    // Released when dropped
+    let _lock = if options.lock {
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(lock_path(dir, &name)?)?;
+        // Not `lock.lock()`: `File::lock` needs Rust 1.89
+        FileExt::lock_exclusive(&lock)?;
+        Some(lock)
+    } else {
+        None
+    };
+
+    if options.compare_and_swap {
+        let current = match fs::read_to_string(path) {
+            Ok(current) => Some(content_hash(&current)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        if current != faf.content_hash() {
+            return Err(FafError::ChangedOnDisk(path.display().to_string()));
+        }
+    }
+
+    if options.backup {
+        match fs::copy(path, dir.join(format!("{}.bak", name))) {
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    write_atomic(path, content.as_bytes())?;
+    Ok(content_hash(&content))
+}
+
+/// Replace `path` with `content` through a fsynced temporary file and a rename
+///
+/// The building block of [`write_file`], for content that is not a
+/// [`FafFile`] (formatted text, compiled `.fafb`). No lock, no backup.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use faf_rust_sdk::write_atomic;
+///
+/// write_atomic("project.fafb", &[0u8; 32]).unwrap();
+/// ```
+pub fn write_atomic<P: AsRef<Path>>(path: P, content: &[u8]) -> io::Result<()> {
+    let path = path.as_ref();
+    let (dir, name) = split_path(path)?;
+    let temp = dir.join(format!(
+        ".{}.{}-{}.tmp",
+        name,
+        process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let written = write_temp(&temp, content, path).and_then(|()| fs::rename(&temp, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    // Make the rename itself durable
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+/// Directory (`.` for a bare name) and file name of a target path
+fn split_path(path: &Path) -> io::Result<(&Path, String)> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?
+        .to_string_lossy()
+        .to_string();
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    Ok((dir, name))
+}
+
+/// Lock file for `dir/name`, shared by every writer of that file
+fn lock_path(dir: &Path, name: &str) -> io::Result<PathBuf> {
+    let target = fs::canonicalize(dir)?.join(name);
+    let hash = content_hash(&target.to_string_lossy());
+    let hex: String = hash[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(std::env::temp_dir().join(format!("faf-{}.lock", hex)))
+}
+
+/// Create `temp` with `content` and the permissions of `target`, and fsync it
+fn write_temp(temp: &Path, content: &[u8], target: &Path) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(temp)?;
+    if let Ok(metadata) = fs::metadata(target) {
+        file.set_permissions(metadata.permissions())?;
+    }
+    file.write_all(content)?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, parse_file};
+    use tempfile::TempDir;
+
+    const CONTENT: &str = "faf_version: 2.5.0\nproject:\n  name: writer\n";
+
+    #[test]
+    fn test_write_and_backup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.faf");
+        let mut faf = parse(CONTENT).unwrap();
+
+        let hash = write_file(&faf, &path, &WriteOptions::default()).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        assert_eq!(parse_file(&path).unwrap().content_hash(), Some(hash));
+        assert_eq!(faf.content_hash(), None);
+        assert_eq!(parse(&written).unwrap().project_name(), "writer");
+        assert!(!dir.path().join("project.faf.bak").exists());
+
+        faf.data.project.name = "rewritten".to_string();
+        let options = WriteOptions {
+            backup: true,
+            ..WriteOptions::default()
+        };
+        write_file(&faf, &path, &options).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("project.faf.bak")).unwrap(),
+            written
+        );
+        assert_eq!(parse_file(&path).unwrap().project_name(), "rewritten");
+
+        // Only the target and its backup remain
+        let mut names: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["project.faf", "project.faf.bak"]);
+    }
+
+    #[test]
+    fn test_compare_and_swap() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.faf");
+        let cas = WriteOptions {
+            compare_and_swap: true,
+            ..WriteOptions::default()
+        };
+
+        // Parsed from text that was never on disk: the file must not exist
+        let fresh = parse(CONTENT).unwrap();
+        write_file(&fresh, &path, &cas).unwrap();
+        assert!(matches!(
+            write_file(&fresh, &path, &cas),
+            Err(FafError::ChangedOnDisk(_))
+        ));
+
+        let mut faf = parse_file(&path).unwrap();
+        faf.set_content_hash(Some(write_file(&faf, &path, &cas).unwrap()));
+        faf.set_content_hash(Some(write_file(&faf, &path, &cas).unwrap()));
+
+        // Someone else edits the file in between
+        fs::write(&path, CONTENT.replace("writer", "other")).unwrap();
+        assert!(matches!(
+            write_file(&faf, &path, &cas),
+            Err(FafError::ChangedOnDisk(_))
+        ));
+        assert_eq!(parse_file(&path).unwrap().project_name(), "other");
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("project.fafb");
+        fs::write(&path, b"old").unwrap();
+        write_atomic(&path, b"new bytes").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new bytes");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        assert!(write_atomic(dir.path().join("missing/project.fafb"), b"x").is_err());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,161 @@
+//! `faf` binary end-to-end tests (run with `--features cli`)
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const FULL_FAF: &str = include_str!("../examples/full.faf");
+
+fn faf(dir: &Path, args: &[&str]) -> Output {
+    faf_with_env(dir, args, None)
+}
+
+fn faf_with_env(dir: &Path, args: &[&str], faf_path: Option<&Path>) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_faf"));
+    command.args(args).current_dir(dir).env_remove("FAF_PATH");
+    if let Some(path) = faf_path {
+        command.env("FAF_PATH", path);
+    }
+    command.output().expect("faf binary runs")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_validate_exit_codes_and_json() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("project.faf"), FULL_FAF).unwrap();
+    fs::write(
+        dir.path().join("broken.faf"),
+        "faf_version: 2.5.0\nproject:\n  name: ''",
+    )
+    .unwrap();
+
+    let ok = faf(dir.path(), &["validate"]);
+    assert_eq!(ok.status.code(), Some(0));
+    assert!(stdout(&ok).contains("valid"));
+
+    let invalid = faf(dir.path(), &["validate", "broken.faf", "--format", "json"]);
+    assert_eq!(invalid.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_str(&stdout(&invalid)).unwrap();
+    assert_eq!(report["valid"], false);
+    assert!(!report["diagnostics"].as_array().unwrap().is_empty());
+
+    assert_eq!(
+        faf(dir.path(), &["validate", "missing.faf"]).status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_find_score_and_compress() {
+    let dir = TempDir::new().unwrap();
+    let nested = dir.path().join("src/deep");
+    fs::create_dir_all(&nested).unwrap();
+
+    assert_eq!(faf(&nested, &["find"]).status.code(), Some(1));
+    fs::write(dir.path().join("project.faf"), FULL_FAF).unwrap();
+    let found = faf(&nested, &["find"]);
+    assert_eq!(found.status.code(), Some(0));
+    assert!(stdout(&found).trim().ends_with("project.faf"));
+    fs::write(dir.path().join(".faf"), FULL_FAF).unwrap();
+    let found = faf(&nested, &["find"]);
+    assert!(String::from_utf8_lossy(&found.stderr).contains(".faf is shadowed"));
+
+    assert!(stdout(&faf(&nested, &["score"])).contains('%'));
+
+    // FAF_PATH overrides discovery for find and every command defaulting its path
+    let elsewhere = dir.path().join("elsewhere.faf");
+    fs::write(
+        &elsewhere,
+        "faf_version: 2.5.0\nproject:\n  name: elsewhere\n",
+    )
+    .unwrap();
+    let found = faf_with_env(&nested, &["find"], Some(&elsewhere));
+    assert!(stdout(&found).trim().ends_with("elsewhere.faf"));
+    let minimal = faf_with_env(
+        &nested,
+        &["compress", "--level", "minimal"],
+        Some(&elsewhere),
+    );
+    assert!(stdout(&minimal).contains("name: elsewhere"));
+    let missing = dir.path().join("missing.faf");
+    assert_eq!(
+        faf_with_env(&nested, &["find"], Some(&missing))
+            .status
+            .code(),
+        Some(1)
+    );
+
+    let minimal = stdout(&faf(dir.path(), &["compress", "--level", "minimal"]));
+    assert!(minimal.contains("full-example"));
+    assert!(!minimal.contains("human_context"));
+    let budget = stdout(&faf(dir.path(), &["compress", "--budget", "40"]));
+    assert!(budget.len() < FULL_FAF.len());
+}
+
+#[test]
+fn test_fmt_diff_compile_export() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("project.faf");
+    fs::write(&path, FULL_FAF).unwrap();
+
+    // The example puts project after the ai_* fields; fmt moves it up
+    assert_eq!(faf(dir.path(), &["fmt", "--check"]).status.code(), Some(1));
+    assert_eq!(faf(dir.path(), &["fmt"]).status.code(), Some(0));
+    let formatted = fs::read_to_string(&path).unwrap();
+    assert!(formatted.starts_with("faf_version: 2.5.0\n\nproject:\n"));
+    assert_eq!(faf(dir.path(), &["fmt", "--check"]).status.code(), Some(0));
+
+    fs::write(
+        &path,
+        formatted.replace("ai_score: \"85%\"", "ai_score: '85%'"),
+    )
+    .unwrap();
+    assert_eq!(faf(dir.path(), &["fmt", "--check"]).status.code(), Some(1));
+    assert_eq!(faf(dir.path(), &["fmt"]).status.code(), Some(0));
+    assert_eq!(fs::read_to_string(&path).unwrap(), formatted);
+
+    // YAML the formatter cannot lay out fails the check instead of passing it
+    let aliased = dir.path().join("aliased.faf");
+    fs::write(
+        &aliased,
+        "tags: [&name demo]\nfaf_version: 2.5.0\nproject:\n  name: *name\n",
+    )
+    .unwrap();
+    let check = faf(dir.path(), &["fmt", "aliased.faf", "--check"]);
+    assert_eq!(check.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&check.stderr).contains("Cannot format"));
+
+    fs::write(
+        dir.path().join("new.faf"),
+        FULL_FAF.replace("\"85%\"", "\"90%\""),
+    )
+    .unwrap();
+    let summary = faf(
+        dir.path(),
+        &["diff", "project.faf", "new.faf", "--format", "summary"],
+    );
+    assert_eq!(stdout(&summary).trim(), "ai_score: 85% → 90%");
+
+    assert_eq!(faf(dir.path(), &["compile"]).status.code(), Some(0));
+    let bytes = fs::read(dir.path().join("project.fafb")).unwrap();
+    assert_eq!(&bytes[0..4], b"FAFB");
+
+    assert_eq!(
+        faf(dir.path(), &["export", "--to", "claude-md"])
+            .status
+            .code(),
+        Some(0)
+    );
+    assert!(fs::read_to_string(dir.path().join("CLAUDE.md"))
+        .unwrap()
+        .contains("faf:begin"));
+    assert_eq!(
+        faf(dir.path(), &["export", "--to", "nope"]).status.code(),
+        Some(2)
+    );
+}
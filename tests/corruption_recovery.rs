@@ -3,7 +3,10 @@
 //! Demonstrates FAF's resilience to file corruption and self-healing capabilities.
 //! Key showcase for xAI integration testing.
 
-use faf_rust_sdk::{parse, validate, find_and_parse};
+use faf_rust_sdk::{
+    diff, fix, merge3, parse, parse_file, validate, find_and_parse, write_file, FafError, FixOptions,
+    Resolution, WriteOptions,
+};
 use std::fs;
 use tempfile::TempDir;
 
@@ -110,6 +113,7 @@ project:
 }
 
 #[test]
+#[allow(clippy::len_zero)]
 fn test_corruption_recovery_workflow() {
     let temp = TempDir::new().unwrap();
     let faf_path = temp.path().join("project.faf");
@@ -157,6 +161,45 @@ project:
     println!("\n🏆 CORRUPTION RECOVERY WORKFLOW COMPLETE");
 }
 
+#[test]
+fn test_self_heal_with_fix() {
+    // Drifted but parseable: sloppy score, duplicated tags and key_files
+    let drifted = r#"
+faf_version: 2.5.0
+ai_score: "85 %"
+
+project:
+  name: drifted
+  goal: Heal without rewriting by hand
+
+instant_context:
+  what_building: Resilient AI context system
+  tech_stack: Rust
+  key_files:
+    - src/lib.rs
+    - src/lib.rs
+
+tags:
+  - rust
+  - rust
+"#;
+
+    let faf = parse(drifted).unwrap();
+    assert_eq!(faf.score(), None);
+    let before = validate(&faf).warnings.len();
+
+    let (healed, applied) = fix(&faf, &FixOptions::default());
+    let after = validate(&healed).warnings.len();
+
+    assert_eq!(healed.score(), Some(85));
+    assert!(after < before, "Fixes should clear warnings");
+    for f in &applied {
+        println!("🔧 {}: {}", f.path, f.description);
+    }
+
+    println!("\n✅ SELF-HEAL: {} fixes, warnings {} → {}", applied.len(), before, after);
+}
+
 #[test]
 fn test_bisync_conflict_detection() {
     // Simulate bi-sync scenario: two versions of same project
@@ -203,9 +246,58 @@ instant_context:
     assert!(goal_changed, "Should detect goal conflict");
     assert!(score_diff > 0, "Should detect score difference");
 
+    let changes = diff(&faf_a.data, &faf_b.data);
+    println!("   Summary: {}", changes.summary());
+    assert!(changes.get("project.goal").is_some());
+    assert!(changes.get("ai_score").is_some());
+    assert!(changes.get("project.name").is_none());
+    assert_eq!(
+        changes.summary(),
+        "ai_score: 80% → 85%; tech_stack: Rust → Rust, Python; \
+         what_building: Feature A → Feature B; goal: Version A - local changes → Version B - remote changes"
+    );
+
     println!("\n✅ BI-SYNC CONFLICT DETECTION WORKING");
 }
 
+#[test]
+fn test_bisync_three_way_merge() {
+    let base = parse(VALID_FAF).unwrap();
+    // Agent refreshes the score, human rewrites the goal and both touch key_files
+    let agent = parse(
+        &VALID_FAF
+            .replace("ai_score: 85%", "ai_score: 90%")
+            .replace("    - src/parser.rs", "    - src/parser.rs\n    - src/sync.rs"),
+    )
+    .unwrap();
+    let human = parse(
+        &VALID_FAF
+            .replace("goal: Demonstrate corruption recovery", "goal: Merge concurrent edits")
+            .replace("    - src/lib.rs\n", ""),
+    )
+    .unwrap();
+
+    let result = merge3(&base.data, &agent.data, &human.data);
+    assert!(result.is_clean(), "conflicts: {:?}", result.conflicts);
+    let merged = &result.merged;
+    assert_eq!(merged.ai_score.as_deref(), Some("90%"));
+    assert_eq!(merged.project.goal.as_deref(), Some("Merge concurrent edits"));
+    assert_eq!(
+        merged.instant_context.as_ref().unwrap().key_files,
+        vec!["src/parser.rs", "src/sync.rs"]
+    );
+
+    // Both sides edit the goal: a real conflict
+    let agent = parse(&VALID_FAF.replace("Demonstrate corruption recovery", "Agent goal")).unwrap();
+    let result = merge3(&base.data, &agent.data, &human.data);
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].path, "project.goal");
+
+    let resolved = result.resolve(Resolution::Theirs).unwrap();
+    assert_eq!(resolved.project.goal.as_deref(), Some("Merge concurrent edits"));
+    println!("\n✅ BI-SYNC THREE-WAY MERGE WORKING");
+}
+
 #[test]
 fn test_unicode_corruption_resilience() {
     // Test with Unicode that might get corrupted in transit
@@ -293,3 +385,45 @@ project:
     println!("✅ Rapid modification test: {}/100 successful parses", success_count);
     assert!(success_count >= 95, "Should handle rapid modifications reliably");
 }
+
+#[test]
+fn test_concurrent_writers_never_corrupt() {
+    let temp = TempDir::new().unwrap();
+    let faf_path = temp.path().join("project.faf");
+    fs::write(&faf_path, VALID_FAF).unwrap();
+
+    // Agents and a human all saving at once, while a reader keeps parsing
+    let writers: Vec<_> = (0..4)
+        .map(|writer| {
+            let path = faf_path.clone();
+            std::thread::spawn(move || {
+                let mut faf = parse(VALID_FAF).unwrap();
+                for i in 0..25 {
+                    faf.data.project.goal = Some(format!("Writer {} iteration {}", writer, i));
+                    write_file(&faf, &path, &WriteOptions::default()).unwrap();
+                }
+            })
+        })
+        .collect();
+    let mut reads = 0;
+    while writers.iter().any(|w| !w.is_finished()) {
+        let faf = parse_file(&faf_path).expect("never half-written");
+        assert!(validate(&faf).valid);
+        reads += 1;
+    }
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    // Compare-and-swap: the second of two agents based on the same read loses
+    let cas = WriteOptions { compare_and_swap: true, ..WriteOptions::default() };
+    let mut agent_a = parse_file(&faf_path).unwrap();
+    let mut agent_b = agent_a.clone();
+    agent_a.data.project.goal = Some("Agent A".to_string());
+    agent_b.data.project.goal = Some("Agent B".to_string());
+    write_file(&agent_a, &faf_path, &cas).unwrap();
+    assert!(matches!(write_file(&agent_b, &faf_path, &cas), Err(FafError::ChangedOnDisk(_))));
+    assert_eq!(parse_file(&faf_path).unwrap().goal(), Some("Agent A"));
+
+    println!("✅ Concurrent writes: {} clean reads, lost update rejected", reads);
+}
@@ -318,24 +318,44 @@ faf_version: 2.5.0
 project:
   name: test
   goal: Testing
+  main_language: Rust
+  approach: TDD
+  version: 1.0.0
+  license: MIT
 instant_context:
-  what_building: App
+  what_building: Test
   tech_stack: Rust
+  deployment: crates.io
   key_files:
     - main.rs
+  commands:
+    test: cargo test
 stack:
+  frontend: None
   backend: Rust
+  database: SQLite
+  infrastructure: GitHub
+  build_tool: Cargo
+  testing: cargo test
+  cicd: GitHub Actions
 human_context:
-  who: Devs
+  who: Developers
+  what: Test app
+  why: Testing
+  how: Carefully
+  where: GitHub
+  when: 2025
 tags:
   - rust
 state:
   phase: dev
+preferences:
+  testing: strict
 "#;
     let faf = parse(content).unwrap();
     let result = validate(&faf);
     assert!(result.valid);
-    // Should max out at 100
+    // All 24 slots filled (plus extras) - should max out at 100
     assert_eq!(result.score, 100);
 }
 
@@ -349,8 +369,8 @@ project:
     let faf = parse(content).unwrap();
     let result = validate(&faf);
     assert!(result.valid);
-    // Only faf_version (10) + project.name (10) = 20
-    assert_eq!(result.score, 20);
+    // Only project.name: 1 of 24 slots
+    assert_eq!(result.score, 4);
 }
 
 // =============================================================================